@@ -0,0 +1,40 @@
+import { createHash } from "crypto";
+
+// Must match MAX_TITLE_LEN / MAX_URI_LEN in programs/governance
+export const MAX_TITLE_LEN = 64;
+export const MAX_URI_LEN = 200;
+
+/**
+ * sha256 of the full off-chain proposal document, as stored in
+ * `Proposal.content_hash`. Strings are hashed as UTF-8.
+ */
+export function hashProposalDocument(document: string | Uint8Array): Buffer {
+  return createHash("sha256").update(document).digest();
+}
+
+/**
+ * Check a document fetched from `Proposal.uri` against the on-chain hash.
+ */
+export function verifyProposalDocument(
+  document: string | Uint8Array,
+  contentHash: Uint8Array | number[]
+): boolean {
+  const expected = Buffer.from(contentHash);
+  return expected.length === 32 && hashProposalDocument(document).equals(expected);
+}
+
+/**
+ * Mirror the program's length checks so invalid proposals fail before sending.
+ */
+export function validateProposalMetadata(title: string, uri: string): void {
+  const titleLen = Buffer.byteLength(title, "utf8");
+  if (titleLen === 0) {
+    throw new Error("Proposal title is empty");
+  }
+  if (titleLen > MAX_TITLE_LEN) {
+    throw new Error(`Proposal title exceeds ${MAX_TITLE_LEN} bytes`);
+  }
+  if (Buffer.byteLength(uri, "utf8") > MAX_URI_LEN) {
+    throw new Error(`Proposal URI exceeds ${MAX_URI_LEN} bytes`);
+  }
+}
@@ -0,0 +1,574 @@
+//! Rust client for the Arkly programs. It builds instructions from each program's
+//! generated `accounts` and `instruction` types, derives PDAs with the program IDs
+//! filled in, fetches and deserializes accounts and decodes events from transaction
+//! logs. Account fetching works against any [`fetch::AccountFetcher`]: JSON-RPC (for
+//! `solana-test-validator`) with the `rpc` feature, or an in-process bank with `banks`.
+
+use anchor_lang::prelude::Pubkey;
+
+pub use arkly_common;
+pub use arkly_token;
+pub use governance;
+pub use property_vault;
+pub use yield_distributor;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    #[error(transparent)]
+    Anchor(Box<anchor_lang::error::Error>),
+    #[error("invalid borsh data: {0}")]
+    Deserialize(#[from] std::io::Error),
+    #[error("invalid base64 in program log: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Rpc(#[from] solana_client::client_error::ClientError),
+    #[cfg(feature = "banks")]
+    #[error(transparent)]
+    Banks(#[from] solana_banks_client::BanksClientError),
+}
+
+// Boxed because anchor's `Error` would make every `Result<_, ClientError>` large
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::Anchor(Box::new(error))
+    }
+}
+
+/// PDA addresses with the owning program's ID filled in
+pub mod pda {
+    use anchor_lang::prelude::Pubkey;
+
+    macro_rules! pdas {
+        ($program_id:expr; $($name:ident($($arg:ident: $ty:ty),*);)*) => {
+            $(
+                pub fn $name($($arg: $ty),*) -> Pubkey {
+                    arkly_common::pda::$name(&$program_id, $($arg),*).0
+                }
+            )*
+        };
+    }
+
+    pub mod arkly_token {
+        use super::*;
+
+        pdas! { ::arkly_token::ID;
+            token_info();
+            sale_round(allocation_type: u8, round_index: u32);
+            round_escrow(sale_round: &Pubkey, mint: &Pubkey);
+            user_purchase(user: &Pubkey, allocation_type: u8);
+            vesting_grant(beneficiary: &Pubkey, allocation_type: u8);
+            airdrop_campaign(id: u64);
+            airdrop_claim(campaign: &Pubkey, user: &Pubkey);
+            milestone(id: u64);
+            liquidity_arkly();
+            liquidity_quote(quote_mint: &Pubkey);
+            liquidity_lock(lp_mint: &Pubkey);
+            lp_lock_vault(lp_mint: &Pubkey);
+            buyback_config();
+            buyback_arkly();
+            revenue_vault(quote_mint: &Pubkey);
+            position_mint(lot: &Pubkey);
+            position_account(position_mint: &Pubkey);
+        }
+    }
+
+    pub mod governance {
+        use super::*;
+
+        pdas! { ::governance::ID;
+            governance();
+            proposal(id: u64);
+            voter_record(proposal: &Pubkey, voter: &Pubkey);
+            stake(user: &Pubkey);
+            council_action(id: u64);
+        }
+    }
+
+    pub mod property_vault {
+        use super::*;
+
+        pdas! { ::property_vault::ID;
+            property(property_id: &str);
+            investor(property: &Pubkey, investor: &Pubkey);
+            yield_distribution(property: &Pubkey, distributed_at: i64);
+            yield_claim(yield_distribution: &Pubkey, investor: &Pubkey);
+            property_proposal(property: &Pubkey, id: u64);
+            property_vote(proposal: &Pubkey, voter: &Pubkey);
+            property_vote_escrow(proposal: &Pubkey);
+        }
+    }
+
+    pub mod yield_distributor {
+        use super::*;
+
+        pdas! { ::yield_distributor::ID;
+            yield_pool(pool_id: &str);
+            distribution(snapshot_id: &str);
+            claim_record(distribution: &Pubkey, claimer: &Pubkey);
+        }
+    }
+}
+
+/// One builder per instruction, taking the program's generated `accounts::*` and
+/// `instruction::*` structs. Remaining accounts can be appended to the returned
+/// instruction's `accounts`.
+pub mod ix {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+
+    /// Build an instruction for any program from its typed accounts and arguments
+    pub fn build(program_id: Pubkey, accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: accounts.to_account_metas(None),
+            data: args.data(),
+        }
+    }
+
+    macro_rules! builders {
+        ($program:ident; $($name:ident($accounts:ident, $args:ident);)*) => {
+            $(
+                pub fn $name(
+                    accounts: ::$program::accounts::$accounts,
+                    args: ::$program::instruction::$args,
+                ) -> Instruction {
+                    super::build(::$program::ID, accounts, args)
+                }
+            )*
+        };
+    }
+
+    pub mod arkly_token {
+        use super::*;
+
+        builders! { arkly_token;
+            initialize_token(InitializeToken, InitializeToken);
+            purchase_presale(PurchasePresale, PurchasePresale);
+            purchase_presale_sol(PurchasePresaleSol, PurchasePresaleSol);
+            set_sol_payment(SetSolPayment, SetSolPayment);
+            add_payment_mint(AddPaymentMint, AddPaymentMint);
+            remove_payment_mint(RemovePaymentMint, RemovePaymentMint);
+            create_sale_round(CreateSaleRound, CreateSaleRound);
+            set_round_paused(UpdateSaleRound, SetRoundPaused);
+            finalize_round(UpdateSaleRound, FinalizeRound);
+            withdraw_round_proceeds(WithdrawRoundProceeds, WithdrawRoundProceeds);
+            withdraw_round_proceeds_sol(WithdrawRoundProceedsSol, WithdrawRoundProceedsSol);
+            refund_presale(RefundPresale, RefundPresale);
+            refund_presale_sol(RefundPresaleSol, RefundPresaleSol);
+            set_tge_timestamp(SetTgeTimestamp, SetTgeTimestamp);
+            set_governance(SetGovernance, SetGovernance);
+            claim_vested_tokens(ClaimVestedTokens, ClaimVestedTokens);
+            claim_all_vested_tokens(ClaimAllVestedTokens, ClaimAllVestedTokens);
+            create_vesting_grant(CreateVestingGrant, CreateVestingGrant);
+            revoke_grant(RevokeGrant, RevokeGrant);
+            create_airdrop_campaign(CreateAirdropCampaign, CreateAirdropCampaign);
+            claim_airdrop(ClaimAirdrop, ClaimAirdrop);
+            sweep_airdrop(SweepAirdrop, SweepAirdrop);
+            define_milestone(DefineMilestone, DefineMilestone);
+            attest_milestone(AttestMilestone, AttestMilestone);
+            set_amm_program(SetAmmProgram, SetAmmProgram);
+            bootstrap_liquidity(BootstrapLiquidity, BootstrapLiquidity);
+            withdraw_locked_lp(WithdrawLockedLp, WithdrawLockedLp);
+            configure_buyback(ConfigureBuyback, ConfigureBuyback);
+            deposit_revenue(DepositRevenue, DepositRevenue);
+            execute_buyback(ExecuteBuyback, ExecuteBuyback);
+            set_position_transfer_approval(SetPositionTransferApproval, SetPositionTransferApproval);
+            tokenize_position(TokenizePosition, TokenizePosition);
+            claim_position(ClaimPosition, ClaimPosition);
+            transfer_position(TransferPosition, TransferPosition);
+            propose_authority(ProposeAuthority, ProposeAuthority);
+            accept_authority(AcceptAuthority, AcceptAuthority);
+            set_role(SetRole, SetRole);
+            set_pause(SetPause, SetPause);
+            quote_presale(QuotePresale, QuotePresale);
+            get_claimable_vested(GetClaimableVested, GetClaimableVested);
+        }
+    }
+
+    pub mod governance {
+        use super::*;
+
+        builders! { governance;
+            initialize_governance(InitializeGovernance, InitializeGovernance);
+            create_proposal(CreateProposal, CreateProposal);
+            vote(Vote, Vote);
+            queue_proposal(QueueProposal, QueueProposal);
+            execute_proposal(ExecuteProposal, ExecuteProposal);
+            stake_tokens(StakeTokens, StakeTokens);
+            unstake_tokens(UnstakeTokens, UnstakeTokens);
+            fund_staking_rewards(FundStakingRewards, FundStakingRewards);
+            claim_staking_rewards(ClaimStakingRewards, ClaimStakingRewards);
+            propose_council_action(ProposeCouncilAction, ProposeCouncilAction);
+            approve_council_action(ApproveCouncilAction, ApproveCouncilAction);
+            execute_council_action(ExecuteCouncilAction, ExecuteCouncilAction);
+            propose_authority(ProposeAuthority, ProposeAuthority);
+            accept_authority(AcceptAuthority, AcceptAuthority);
+        }
+    }
+
+    pub mod property_vault {
+        use super::*;
+
+        builders! { property_vault;
+            initialize_property(InitializeProperty, InitializeProperty);
+            purchase_property_tokens(PurchasePropertyTokens, PurchasePropertyTokens);
+            distribute_yield(DistributeYield, DistributeYield);
+            claim_yield(ClaimYield, ClaimYield);
+            update_property_status(UpdatePropertyStatus, UpdatePropertyStatus);
+            create_property_proposal(CreatePropertyProposal, CreatePropertyProposal);
+            cast_property_vote(CastPropertyVote, CastPropertyVote);
+            finalize_property_proposal(FinalizePropertyProposal, FinalizePropertyProposal);
+            withdraw_property_vote(WithdrawPropertyVote, WithdrawPropertyVote);
+            quote_property_purchase(QuotePropertyPurchase, QuotePropertyPurchase);
+            get_claimable_property_yield(GetClaimablePropertyYield, GetClaimablePropertyYield);
+            propose_authority(ProposeAuthority, ProposeAuthority);
+            accept_authority(AcceptAuthority, AcceptAuthority);
+            set_role(SetRole, SetRole);
+        }
+    }
+
+    pub mod yield_distributor {
+        use super::*;
+
+        builders! { yield_distributor;
+            initialize_yield_pool(InitializeYieldPool, InitializeYieldPool);
+            deposit_yield(DepositYield, DepositYield);
+            create_distribution_snapshot(CreateDistributionSnapshot, CreateDistributionSnapshot);
+            claim_yield(ClaimYield, ClaimYield);
+            batch_process_claims(BatchProcessClaims, BatchProcessClaims);
+            finalize_distribution(FinalizeDistribution, FinalizeDistribution);
+            pause_pool(PausePool, PausePool);
+            resume_pool(ResumePool, ResumePool);
+            get_claimable_distribution(GetClaimableDistribution, GetClaimableDistribution);
+            propose_authority(ProposeAuthority, ProposeAuthority);
+            accept_authority(AcceptAuthority, AcceptAuthority);
+            set_role(SetRole, SetRole);
+        }
+    }
+}
+
+/// Account fetching and deserialization
+pub mod fetch {
+    use super::ClientError;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::AccountDeserialize;
+
+    /// Source of raw account data; `None` when the account does not exist
+    pub trait AccountFetcher {
+        fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+    }
+
+    /// Deserialize an Anchor account, checking its discriminator
+    pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+        Ok(T::try_deserialize(&mut &data[..])?)
+    }
+
+    pub fn fetch_account<T: AccountDeserialize>(
+        fetcher: &impl AccountFetcher,
+        address: &Pubkey,
+    ) -> Result<T, ClientError> {
+        fetch_optional_account(fetcher, address)?.ok_or(ClientError::AccountNotFound(*address))
+    }
+
+    /// Like `fetch_account`, but an account that was never created (e.g. an unclaimed
+    /// claim record) is `None` rather than an error
+    pub fn fetch_optional_account<T: AccountDeserialize>(
+        fetcher: &impl AccountFetcher,
+        address: &Pubkey,
+    ) -> Result<Option<T>, ClientError> {
+        fetcher
+            .account_data(address)?
+            .map(|data| decode_account(&data))
+            .transpose()
+    }
+
+    #[cfg(feature = "rpc")]
+    impl AccountFetcher for solana_client::rpc_client::RpcClient {
+        fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+            let account = self.get_account_with_commitment(address, self.commitment())?.value;
+
+            Ok(account.map(|account| account.data))
+        }
+    }
+
+    /// `fetch_account` for an in-process bank, whose client is async
+    #[cfg(feature = "banks")]
+    pub async fn fetch_account_banks<T: AccountDeserialize>(
+        banks: &mut solana_banks_client::BanksClient,
+        address: &Pubkey,
+    ) -> Result<T, ClientError> {
+        let account = banks
+            .get_account(*address)
+            .await?
+            .ok_or(ClientError::AccountNotFound(*address))?;
+
+        decode_account(&account.data)
+    }
+}
+
+/// Event and return data decoding from transaction log messages
+pub mod events {
+    use super::ClientError;
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::{AnchorDeserialize, Event};
+
+    const DATA_PREFIX: &str = "Program data: ";
+    const RETURN_PREFIX: &str = "Program return: ";
+
+    /// Decode every `E` emitted by `program_id` from a transaction's log messages.
+    /// Data logged by other programs in the same transaction is skipped, so events
+    /// with the same name in two programs are never confused.
+    pub fn parse_events<E: Event>(program_id: &Pubkey, logs: &[String]) -> Result<Vec<E>, ClientError> {
+        let program_id = program_id.to_string();
+        let mut call_stack: Vec<&str> = Vec::new();
+        let mut events = Vec::new();
+
+        for line in logs {
+            if let Some(data) = line.strip_prefix(DATA_PREFIX) {
+                if call_stack.last() != Some(&program_id.as_str()) {
+                    continue;
+                }
+                let bytes = base64::decode(data)?;
+                if bytes.len() >= 8 && bytes[..8] == E::DISCRIMINATOR {
+                    events.push(E::deserialize(&mut &bytes[8..])?);
+                }
+            } else if let Some(rest) = line.strip_prefix("Program ") {
+                let mut words = rest.split_whitespace();
+                match (words.next(), words.next()) {
+                    (Some(id), Some("invoke")) => call_stack.push(id),
+                    (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                        call_stack.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Decode the value a view or quote instruction of `program_id` returned, from the
+    /// logs of a simulated transaction
+    pub fn parse_return_data<T: AnchorDeserialize>(
+        program_id: &Pubkey,
+        logs: &[String],
+    ) -> Result<Option<T>, ClientError> {
+        let program_id = program_id.to_string();
+
+        let data = logs.iter().rev().find_map(|line| {
+            let mut words = line.strip_prefix(RETURN_PREFIX)?.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(id), Some(data)) if id == program_id => Some(data),
+                _ => None,
+            }
+        });
+
+        data.map(|data| decode_return_data(&base64::decode(data)?)).transpose()
+    }
+
+    /// Decode return data already extracted from a simulation result
+    pub fn decode_return_data<T: AnchorDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+        Ok(T::try_from_slice(data)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Discriminator, Event};
+    use std::collections::HashMap;
+
+    struct MapFetcher(HashMap<Pubkey, Vec<u8>>);
+
+    impl fetch::AccountFetcher for MapFetcher {
+        fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+            Ok(self.0.get(address).cloned())
+        }
+    }
+
+    fn stake_account(user: Pubkey) -> governance::StakeAccount {
+        governance::StakeAccount {
+            user,
+            staked_amount: 1_000,
+            last_stake_time: 1_700_000_000,
+            reward_per_token_paid: 42,
+            rewards_earned: 7,
+            voting_lock_until: 1_700_086_400,
+        }
+    }
+
+    #[test]
+    fn builder_sets_program_accounts_and_data() {
+        let governance_pda = pda::governance::governance();
+        let new_authority = Pubkey::new_unique();
+
+        let ix = ix::governance::accept_authority(
+            governance::accounts::AcceptAuthority {
+                governance: governance_pda,
+                new_authority,
+            },
+            governance::instruction::AcceptAuthority {},
+        );
+
+        assert_eq!(ix.program_id, governance::ID);
+        assert_eq!(ix.accounts.len(), 2);
+        assert_eq!(ix.accounts[0].pubkey, governance_pda);
+        assert!(ix.accounts[0].is_writable && !ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[1].pubkey, new_authority);
+        assert!(ix.accounts[1].is_signer && !ix.accounts[1].is_writable);
+        assert_eq!(ix.data, governance::instruction::AcceptAuthority::DISCRIMINATOR.to_vec());
+    }
+
+    #[test]
+    fn builder_encodes_arguments_after_discriminator() {
+        let user = Pubkey::new_unique();
+
+        let ix = ix::governance::unstake_tokens(
+            governance::accounts::UnstakeTokens {
+                governance: pda::governance::governance(),
+                stake_account: pda::governance::stake(&user),
+                user_token_account: Pubkey::new_unique(),
+                governance_vault: Pubkey::new_unique(),
+                user,
+                token_program: Pubkey::new_unique(),
+            },
+            governance::instruction::UnstakeTokens { amount: 500 },
+        );
+
+        let (discriminator, args) = ix.data.split_at(8);
+        assert_eq!(discriminator, governance::instruction::UnstakeTokens::DISCRIMINATOR);
+        assert_eq!(args, 500u64.to_le_bytes());
+    }
+
+    #[test]
+    fn pdas_use_the_owning_program_id() {
+        let user = Pubkey::new_unique();
+        let (expected, _) =
+            Pubkey::find_program_address(&[arkly_common::seeds::STAKE, user.as_ref()], &governance::ID);
+
+        assert_eq!(pda::governance::stake(&user), expected);
+        assert_ne!(
+            pda::arkly_token::token_info(),
+            arkly_common::pda::token_info(&governance::ID).0
+        );
+    }
+
+    #[test]
+    fn fetch_round_trips_an_account() {
+        let user = Pubkey::new_unique();
+        let address = pda::governance::stake(&user);
+        let mut data = Vec::new();
+        stake_account(user).try_serialize(&mut data).unwrap();
+        let fetcher = MapFetcher(HashMap::from([(address, data)]));
+
+        let fetched: governance::StakeAccount = fetch::fetch_account(&fetcher, &address).unwrap();
+
+        assert_eq!(fetched.user, user);
+        assert_eq!(fetched.staked_amount, 1_000);
+        assert_eq!(fetched.reward_per_token_paid, 42);
+        assert_eq!(fetched.voting_lock_until, 1_700_086_400);
+    }
+
+    #[test]
+    fn fetch_reports_missing_and_mistyped_accounts() {
+        let user = Pubkey::new_unique();
+        let address = pda::governance::stake(&user);
+        let mut data = Vec::new();
+        stake_account(user).try_serialize(&mut data).unwrap();
+        let fetcher = MapFetcher(HashMap::from([(address, data)]));
+        let missing = Pubkey::new_unique();
+
+        assert!(matches!(
+            fetch::fetch_account::<governance::StakeAccount>(&fetcher, &missing),
+            Err(ClientError::AccountNotFound(key)) if key == missing
+        ));
+        assert!(fetch::fetch_optional_account::<governance::StakeAccount>(&fetcher, &missing)
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            fetch::fetch_account::<governance::Governance>(&fetcher, &address),
+            Err(ClientError::Anchor(_))
+        ));
+    }
+
+    #[test]
+    fn events_are_decoded_only_for_the_requested_program() {
+        let event = governance::TokensStaked {
+            user: Pubkey::new_unique(),
+            amount: 250,
+            total_staked: 1_250,
+            timestamp: 1_700_000_000,
+        };
+        let data = format!("Program data: {}", base64::encode(event.data()));
+        let other = Pubkey::new_unique();
+
+        let logs = vec![
+            format!("Program {} invoke [1]", governance::ID),
+            data.clone(),
+            format!("Program {} invoke [2]", other),
+            data.clone(),
+            format!("Program {} success", other),
+            "Program log: done".to_string(),
+            format!("Program {} success", governance::ID),
+            data,
+        ];
+
+        let events: Vec<governance::TokensStaked> = events::parse_events(&governance::ID, &logs).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].user, event.user);
+        assert_eq!(events[0].amount, 250);
+        assert_eq!(events[0].total_staked, 1_250);
+    }
+
+    #[test]
+    fn events_of_another_type_are_skipped() {
+        let event = governance::ProposalDefeated { proposal_id: 3 };
+        let logs = vec![
+            format!("Program {} invoke [1]", governance::ID),
+            format!("Program data: {}", base64::encode(event.data())),
+            format!("Program {} success", governance::ID),
+        ];
+
+        let staked: Vec<governance::TokensStaked> = events::parse_events(&governance::ID, &logs).unwrap();
+        let defeated: Vec<governance::ProposalDefeated> =
+            events::parse_events(&governance::ID, &logs).unwrap();
+
+        assert!(staked.is_empty());
+        assert_eq!(defeated.len(), 1);
+        assert_eq!(defeated[0].proposal_id, 3);
+    }
+
+    #[test]
+    fn return_data_is_read_for_the_requested_program() {
+        let other = Pubkey::new_unique();
+        let logs = vec![
+            format!("Program return: {} {}", arkly_token::ID, base64::encode(1_234u64.to_le_bytes())),
+            format!("Program return: {} {}", other, base64::encode(9u64.to_le_bytes())),
+        ];
+
+        let value: Option<u64> = events::parse_return_data(&arkly_token::ID, &logs).unwrap();
+        let absent: Option<u64> = events::parse_return_data(&governance::ID, &logs).unwrap();
+
+        assert_eq!(value, Some(1_234));
+        assert_eq!(absent, None);
+    }
+
+    #[test]
+    fn malformed_log_data_is_an_error() {
+        let logs = vec![
+            format!("Program {} invoke [1]", governance::ID),
+            "Program data: not base64!".to_string(),
+        ];
+
+        assert!(matches!(
+            events::parse_events::<governance::TokensStaked>(&governance::ID, &logs),
+            Err(ClientError::Base64(_))
+        ));
+        assert!(events::decode_return_data::<u64>(&[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,485 @@
+//! Building blocks shared by the Arkly programs and their off-chain clients: the PDA
+//! seed registry and derivation helpers, checked fixed-point math, the delegated role
+//! model, the errors they raise and the authority and role events every program
+//! emits. Other account and argument types stay in each program so that they appear in
+//! its IDL. The shared types do not appear in any program IDL: decode them with
+//! `arkly-client` or the types here. `Role` encodes as its variant index (one byte).
+use anchor_lang::prelude::*;
+
+/// Errors shared by every program, numbered from 9000 so they never collide with a
+/// program's own `ErrorCode`
+#[error_code(offset = 9000)]
+pub enum CommonError {
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Division by zero")]
+    DivisionByZero,
+}
+
+/// PDA seed prefixes, one per account kind
+pub mod seeds {
+    // arkly-token
+    pub const TOKEN_INFO: &[u8] = b"token_info";
+    pub const SALE_ROUND: &[u8] = b"sale_round";
+    pub const ROUND_ESCROW: &[u8] = b"round_escrow";
+    pub const USER_PURCHASE: &[u8] = b"user_purchase";
+    pub const VESTING_GRANT: &[u8] = b"vesting_grant";
+    pub const AIRDROP_CAMPAIGN: &[u8] = b"airdrop_campaign";
+    pub const AIRDROP_CLAIM: &[u8] = b"airdrop_claim";
+    pub const MILESTONE: &[u8] = b"milestone";
+    pub const LIQUIDITY_ARKLY: &[u8] = b"liquidity_arkly";
+    pub const LIQUIDITY_QUOTE: &[u8] = b"liquidity_quote";
+    pub const LIQUIDITY_LOCK: &[u8] = b"liquidity_lock";
+    pub const LP_LOCK_VAULT: &[u8] = b"lp_lock_vault";
+    pub const BUYBACK_CONFIG: &[u8] = b"buyback_config";
+    pub const BUYBACK_ARKLY: &[u8] = b"buyback_arkly";
+    pub const REVENUE_VAULT: &[u8] = b"revenue_vault";
+    pub const POSITION_MINT: &[u8] = b"position_mint";
+    pub const POSITION_ACCOUNT: &[u8] = b"position_account";
+
+    // governance
+    pub const GOVERNANCE: &[u8] = b"governance";
+    pub const PROPOSAL: &[u8] = b"proposal";
+    pub const VOTER_RECORD: &[u8] = b"voter_record";
+    pub const STAKE: &[u8] = b"stake";
+    pub const COUNCIL_ACTION: &[u8] = b"council_action";
+
+    // property-vault
+    pub const PROPERTY: &[u8] = b"property";
+    pub const INVESTOR: &[u8] = b"investor";
+    pub const YIELD_DISTRIBUTION: &[u8] = b"yield_distribution";
+    pub const YIELD_CLAIM: &[u8] = b"yield_claim";
+    pub const PROPERTY_PROPOSAL: &[u8] = b"property_proposal";
+    pub const PROPERTY_VOTE: &[u8] = b"property_vote";
+    pub const PROPERTY_VOTE_ESCROW: &[u8] = b"property_vote_escrow";
+
+    // yield-distributor
+    pub const YIELD_POOL: &[u8] = b"yield_pool";
+    pub const DISTRIBUTION: &[u8] = b"distribution";
+    pub const CLAIM_RECORD: &[u8] = b"claim_record";
+}
+
+/// PDA derivation for every account in the seed registry. `program_id` is the program
+/// owning the account; these work on-chain and off-chain alike.
+pub mod pda {
+    use super::seeds;
+    use anchor_lang::prelude::Pubkey;
+
+    fn find(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    // arkly-token
+
+    pub fn token_info(program_id: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::TOKEN_INFO], program_id)
+    }
+
+    pub fn sale_round(program_id: &Pubkey, allocation_type: u8, round_index: u32) -> (Pubkey, u8) {
+        find(
+            &[seeds::SALE_ROUND, &allocation_type.to_le_bytes(), &round_index.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn round_escrow(program_id: &Pubkey, sale_round: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::ROUND_ESCROW, sale_round.as_ref(), mint.as_ref()], program_id)
+    }
+
+    pub fn user_purchase(program_id: &Pubkey, user: &Pubkey, allocation_type: u8) -> (Pubkey, u8) {
+        find(
+            &[seeds::USER_PURCHASE, user.as_ref(), &allocation_type.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn vesting_grant(program_id: &Pubkey, beneficiary: &Pubkey, allocation_type: u8) -> (Pubkey, u8) {
+        find(
+            &[seeds::VESTING_GRANT, beneficiary.as_ref(), &allocation_type.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn airdrop_campaign(program_id: &Pubkey, id: u64) -> (Pubkey, u8) {
+        find(&[seeds::AIRDROP_CAMPAIGN, &id.to_le_bytes()], program_id)
+    }
+
+    pub fn airdrop_claim(program_id: &Pubkey, campaign: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::AIRDROP_CLAIM, campaign.as_ref(), user.as_ref()], program_id)
+    }
+
+    pub fn milestone(program_id: &Pubkey, id: u64) -> (Pubkey, u8) {
+        find(&[seeds::MILESTONE, &id.to_le_bytes()], program_id)
+    }
+
+    pub fn liquidity_arkly(program_id: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::LIQUIDITY_ARKLY], program_id)
+    }
+
+    pub fn liquidity_quote(program_id: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::LIQUIDITY_QUOTE, quote_mint.as_ref()], program_id)
+    }
+
+    pub fn liquidity_lock(program_id: &Pubkey, lp_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::LIQUIDITY_LOCK, lp_mint.as_ref()], program_id)
+    }
+
+    pub fn lp_lock_vault(program_id: &Pubkey, lp_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::LP_LOCK_VAULT, lp_mint.as_ref()], program_id)
+    }
+
+    pub fn buyback_config(program_id: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::BUYBACK_CONFIG], program_id)
+    }
+
+    pub fn buyback_arkly(program_id: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::BUYBACK_ARKLY], program_id)
+    }
+
+    pub fn revenue_vault(program_id: &Pubkey, quote_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::REVENUE_VAULT, quote_mint.as_ref()], program_id)
+    }
+
+    pub fn position_mint(program_id: &Pubkey, lot: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::POSITION_MINT, lot.as_ref()], program_id)
+    }
+
+    pub fn position_account(program_id: &Pubkey, position_mint: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::POSITION_ACCOUNT, position_mint.as_ref()], program_id)
+    }
+
+    // governance
+
+    pub fn governance(program_id: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::GOVERNANCE], program_id)
+    }
+
+    pub fn proposal(program_id: &Pubkey, id: u64) -> (Pubkey, u8) {
+        find(&[seeds::PROPOSAL, &id.to_le_bytes()], program_id)
+    }
+
+    pub fn voter_record(program_id: &Pubkey, proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::VOTER_RECORD, proposal.as_ref(), voter.as_ref()], program_id)
+    }
+
+    pub fn stake(program_id: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::STAKE, user.as_ref()], program_id)
+    }
+
+    pub fn council_action(program_id: &Pubkey, id: u64) -> (Pubkey, u8) {
+        find(&[seeds::COUNCIL_ACTION, &id.to_le_bytes()], program_id)
+    }
+
+    // property-vault
+
+    pub fn property(program_id: &Pubkey, property_id: &str) -> (Pubkey, u8) {
+        find(&[seeds::PROPERTY, property_id.as_bytes()], program_id)
+    }
+
+    pub fn investor(program_id: &Pubkey, property: &Pubkey, investor: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::INVESTOR, property.as_ref(), investor.as_ref()], program_id)
+    }
+
+    pub fn yield_distribution(program_id: &Pubkey, property: &Pubkey, distributed_at: i64) -> (Pubkey, u8) {
+        find(
+            &[seeds::YIELD_DISTRIBUTION, property.as_ref(), &distributed_at.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn yield_claim(program_id: &Pubkey, yield_distribution: &Pubkey, investor: &Pubkey) -> (Pubkey, u8) {
+        find(
+            &[seeds::YIELD_CLAIM, yield_distribution.as_ref(), investor.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn property_proposal(program_id: &Pubkey, property: &Pubkey, id: u64) -> (Pubkey, u8) {
+        find(
+            &[seeds::PROPERTY_PROPOSAL, property.as_ref(), &id.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn property_vote(program_id: &Pubkey, proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::PROPERTY_VOTE, proposal.as_ref(), voter.as_ref()], program_id)
+    }
+
+    pub fn property_vote_escrow(program_id: &Pubkey, proposal: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::PROPERTY_VOTE_ESCROW, proposal.as_ref()], program_id)
+    }
+
+    // yield-distributor
+
+    pub fn yield_pool(program_id: &Pubkey, pool_id: &str) -> (Pubkey, u8) {
+        find(&[seeds::YIELD_POOL, pool_id.as_bytes()], program_id)
+    }
+
+    pub fn distribution(program_id: &Pubkey, snapshot_id: &str) -> (Pubkey, u8) {
+        find(&[seeds::DISTRIBUTION, snapshot_id.as_bytes()], program_id)
+    }
+
+    pub fn claim_record(program_id: &Pubkey, distribution: &Pubkey, claimer: &Pubkey) -> (Pubkey, u8) {
+        find(&[seeds::CLAIM_RECORD, distribution.as_ref(), claimer.as_ref()], program_id)
+    }
+}
+
+/// Checked arithmetic. Products are taken in u128 and every division states its
+/// rounding direction: payments round up and payouts round down, so rounding never
+/// works against the protocol.
+// Handlers `?` these straight into anchor's `Result`, whose error type is large
+#[allow(clippy::result_large_err)]
+pub mod math {
+    use super::CommonError;
+    use anchor_lang::prelude::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Rounding {
+        Down,
+        Up,
+    }
+
+    pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+        Ok(a.checked_add(b).ok_or(CommonError::MathOverflow)?)
+    }
+
+    pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+        Ok(a.checked_sub(b).ok_or(CommonError::MathOverflow)?)
+    }
+
+    pub fn checked_mul(a: u64, b: u64) -> Result<u64> {
+        Ok(a.checked_mul(b).ok_or(CommonError::MathOverflow)?)
+    }
+
+    /// `numerator / denominator`, rounded as requested
+    pub fn div(numerator: u128, denominator: u128, rounding: Rounding) -> Result<u128> {
+        require!(denominator != 0, CommonError::DivisionByZero);
+        let remainder = numerator % denominator;
+        let round_up = rounding == Rounding::Up && remainder > 0;
+
+        Ok(numerator / denominator + u128::from(round_up))
+    }
+
+    /// `a * b / c` in u128, rounded as requested
+    pub fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Result<u128> {
+        let product = a.checked_mul(b).ok_or(CommonError::MathOverflow)?;
+
+        div(product, c, rounding)
+    }
+
+    /// `a * b / c`, rounded down
+    pub fn mul_div_floor(a: u64, b: u64, c: u64) -> Result<u64> {
+        to_u64(mul_div(a as u128, b as u128, c as u128, Rounding::Down)?)
+    }
+
+    /// `a * b / c`, rounded up
+    pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64> {
+        to_u64(mul_div(a as u128, b as u128, c as u128, Rounding::Up)?)
+    }
+
+    pub fn to_u64(value: u128) -> Result<u64> {
+        u64::try_from(value).map_err(|_| CommonError::MathOverflow.into())
+    }
+}
+
+/// Events emitted by every program's authority handoff and role assignment. `scope`
+/// is the account holding the authority: the token info, governance, property or
+/// yield pool.
+pub mod events {
+    use anchor_lang::prelude::*;
+
+    #[event]
+    pub struct AuthorityProposed {
+        pub scope: Pubkey,
+        pub authority: Pubkey,
+        pub pending_authority: Pubkey,
+    }
+
+    #[event]
+    pub struct AuthorityTransferred {
+        pub scope: Pubkey,
+        pub previous_authority: Pubkey,
+        pub new_authority: Pubkey,
+    }
+
+    /// `role` is the index of the emitting program's `Role` variant
+    #[event]
+    pub struct RoleUpdated {
+        pub scope: Pubkey,
+        pub role: u8,
+        pub key: Pubkey,
+    }
+}
+
+/// Delegated roles and their checks. The admin holds every role implicitly; an
+/// unassigned role is `Pubkey::default()` and matches nobody else.
+pub mod roles {
+    use anchor_lang::prelude::*;
+
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Role {
+        Operator,
+        Pauser,
+        Compliance,
+    }
+
+    /// Keys holding the delegated roles, stored on each program's admin account
+    /// next to its `authority`
+    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq, Debug)]
+    pub struct Roles {
+        pub operator: Pubkey,
+        pub pauser: Pubkey,
+        pub compliance: Pubkey,
+    }
+
+    impl Roles {
+        pub const LEN: usize = 32 + 32 + 32;
+
+        /// Whether `key` may act as `role`, either as its assignee or as the admin
+        pub fn holds(&self, admin: Pubkey, role: Role, key: Pubkey) -> bool {
+            let assignee = match role {
+                Role::Operator => self.operator,
+                Role::Pauser => self.pauser,
+                Role::Compliance => self.compliance,
+            };
+            holds(admin, assignee, key)
+        }
+
+        pub fn set(&mut self, role: Role, key: Pubkey) {
+            match role {
+                Role::Operator => self.operator = key,
+                Role::Pauser => self.pauser = key,
+                Role::Compliance => self.compliance = key,
+            }
+        }
+    }
+
+    /// Whether `key` may act in a role assigned to `assignee` under `admin`
+    pub fn holds(admin: Pubkey, assignee: Pubkey, key: Pubkey) -> bool {
+        key == admin || (assignee != Pubkey::default() && key == assignee)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::math::{self, Rounding};
+    use super::*;
+    use anchor_lang::{Discriminator, Event};
+
+    fn error_code(result: Result<impl std::fmt::Debug>) -> u32 {
+        match result.unwrap_err() {
+            Error::AnchorError(error) => error.error_code_number,
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    #[test]
+    fn checked_ops_report_overflow() {
+        let overflow = 9000 + CommonError::MathOverflow as u32;
+
+        assert_eq!(math::checked_add(u64::MAX - 1, 1).unwrap(), u64::MAX);
+        assert_eq!(error_code(math::checked_add(u64::MAX, 1)), overflow);
+        assert_eq!(math::checked_sub(1, 1).unwrap(), 0);
+        assert_eq!(error_code(math::checked_sub(0, 1)), overflow);
+        assert_eq!(math::checked_mul(u64::MAX, 1).unwrap(), u64::MAX);
+        assert_eq!(error_code(math::checked_mul(u64::MAX, 2)), overflow);
+        assert_eq!(error_code(math::to_u64(u64::MAX as u128 + 1)), overflow);
+    }
+
+    #[test]
+    fn div_rounds_as_requested() {
+        assert_eq!(math::div(7, 2, Rounding::Down).unwrap(), 3);
+        assert_eq!(math::div(7, 2, Rounding::Up).unwrap(), 4);
+        assert_eq!(math::div(8, 2, Rounding::Up).unwrap(), 4);
+        assert_eq!(math::div(0, 3, Rounding::Up).unwrap(), 0);
+        assert_eq!(
+            error_code(math::div(1, 0, Rounding::Down)),
+            9000 + CommonError::DivisionByZero as u32
+        );
+    }
+
+    #[test]
+    fn mul_div_keeps_the_product_in_u128() {
+        assert_eq!(math::mul_div_floor(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(math::mul_div_floor(10, 1, 3).unwrap(), 3);
+        assert_eq!(math::mul_div_ceil(10, 1, 3).unwrap(), 4);
+        assert_eq!(math::mul_div_ceil(9, 1, 3).unwrap(), 3);
+        assert_eq!(
+            error_code(math::mul_div(u128::MAX, 2, 1, Rounding::Down)),
+            9000 + CommonError::MathOverflow as u32
+        );
+        assert_eq!(
+            error_code(math::mul_div_floor(u64::MAX, 2, 1)),
+            9000 + CommonError::MathOverflow as u32
+        );
+        assert_eq!(
+            error_code(math::mul_div_ceil(1, 1, 0)),
+            9000 + CommonError::DivisionByZero as u32
+        );
+    }
+
+    #[test]
+    fn pda_helpers_match_their_seeds() {
+        let program_id = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+        let expected = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program_id);
+
+        assert_eq!(
+            pda::sale_round(&program_id, 1, 2),
+            expected(&[seeds::SALE_ROUND, &[1], &2u32.to_le_bytes()])
+        );
+        assert_ne!(pda::sale_round(&program_id, 1, 2), pda::sale_round(&program_id, 1, 3));
+        assert_eq!(pda::stake(&program_id, &user), expected(&[seeds::STAKE, user.as_ref()]));
+        assert_eq!(
+            pda::distribution(&program_id, "snapshot-1"),
+            expected(&[seeds::DISTRIBUTION, b"snapshot-1"])
+        );
+    }
+
+    #[test]
+    fn roles_hold_for_admin_and_assignee_only() {
+        let admin = Pubkey::new_unique();
+        let assignee = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        assert!(roles::holds(admin, assignee, admin));
+        assert!(roles::holds(admin, assignee, assignee));
+        assert!(!roles::holds(admin, assignee, other));
+        assert!(roles::holds(admin, Pubkey::default(), admin));
+        assert!(!roles::holds(admin, Pubkey::default(), Pubkey::default()));
+    }
+
+    #[test]
+    fn assigned_roles_are_held_per_role() {
+        let admin = Pubkey::new_unique();
+        let pauser = Pubkey::new_unique();
+        let mut assigned = roles::Roles::default();
+
+        assigned.set(roles::Role::Pauser, pauser);
+        assert!(assigned.holds(admin, roles::Role::Pauser, pauser));
+        assert!(!assigned.holds(admin, roles::Role::Operator, pauser));
+        assert!(assigned.holds(admin, roles::Role::Compliance, admin));
+
+        assigned.set(roles::Role::Pauser, Pubkey::default());
+        assert!(!assigned.holds(admin, roles::Role::Pauser, pauser));
+        assert_eq!(roles::Roles::LEN, roles::Roles::default().try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn events_round_trip_through_their_log_data() {
+        let event = events::RoleUpdated {
+            scope: Pubkey::new_unique(),
+            role: 2,
+            key: Pubkey::new_unique(),
+        };
+        let data = event.data();
+
+        assert_eq!(&data[..8], &events::RoleUpdated::DISCRIMINATOR);
+        let decoded = events::RoleUpdated::try_from_slice(&data[8..]).unwrap();
+        assert_eq!((decoded.scope, decoded.role, decoded.key), (event.scope, event.role, event.key));
+    }
+}
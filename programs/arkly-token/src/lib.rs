@@ -1,5 +1,20 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use anchor_spl::token::{self, Burn, FreezeAccount, Mint, ThawAccount, Token, TokenAccount, Transfer};
+use arkly_common::events::{AuthorityProposed, AuthorityTransferred, RoleUpdated};
+use arkly_common::math::{self, checked_add, checked_sub, mul_div_floor, Rounding};
+use arkly_common::roles::{Role, Roles};
+use arkly_common::{seeds, CommonError};
 
 declare_id!("ARKLyT0k3nM1nt7h1s1sY0urT0k3nPr0gr4mId3nt1f13r");
 
@@ -18,320 +33,3484 @@ pub mod arkly_token {
         token_info.circulating_supply = 0;
         token_info.decimals = decimals;
         token_info.authority = ctx.accounts.authority.key();
+        token_info.pending_authority = Pubkey::default();
+        token_info.roles = Roles::default(); // Assigned via `set_role`
+        token_info.paused = PauseFlags::default();
         token_info.mint = ctx.accounts.mint.key();
+        token_info.total_minted = 0;
+        token_info.total_burned = 0;
+        token_info.tge_timestamp = 0; // Set later via `set_tge_timestamp`
+        token_info.governance = Pubkey::default(); // Set later via `set_governance`
+        token_info.accepted_payments = Vec::new(); // Added via `add_payment_mint`
+        token_info.sol_payment = SolPaymentConfig::default(); // Set via `set_sol_payment`
+        token_info.airdrop_campaign_count = 0;
+        token_info.milestone_count = 0;
+        token_info.presale_round_succeeded = [false; 2];
+        token_info.sale_round_count = [0; 2];
+        token_info.sale_round_open = [false; 2];
+        token_info.amm_program = Pubkey::default(); // Set via `set_amm_program`
+        token_info.position_transfer_approval = false; // Set via `set_position_transfer_approval`
         
         // Initialize tokenomics allocations
-        token_info.allocations = TokenomicsAllocations {
-            seed_round: AllocationInfo {
-                amount: total_supply * 12 / 100, // 12%
-                price: 83_000_000, // $0.083 in micro-dollars
-                cliff_months: 6,
-                vesting_months: 12,
-                released: 0,
-            },
-            public_presale: AllocationInfo {
-                amount: total_supply * 75 / 1000, // 7.5%
-                price: 100_000_000, // $0.10 in micro-dollars
-                cliff_months: 0,
-                vesting_months: 0, // 100% at TGE
-                released: 0,
-            },
-            liquidity_pool: AllocationInfo {
-                amount: total_supply * 10 / 100, // 10%
-                price: 150_000_000, // $0.15 listing price
-                cliff_months: 0,
-                vesting_months: 1, // 50% at TGE, 50% after 30 days
-                released: 0,
-            },
-            team_advisors: AllocationInfo {
-                amount: total_supply * 15 / 100, // 15%
-                price: 0,
-                cliff_months: 12,
-                vesting_months: 24,
-                released: 0,
-            },
-            ecosystem_rewards: AllocationInfo {
-                amount: total_supply * 25 / 100, // 25%
-                price: 0,
-                cliff_months: 0,
-                vesting_months: 36,
-                released: 0,
-            },
-            treasury_dev: AllocationInfo {
-                amount: total_supply * 20 / 100, // 20%
-                price: 0,
-                cliff_months: 0,
-                vesting_months: 0, // Roadmap-based
-                released: 0,
-            },
-            strategic_partners: AllocationInfo {
-                amount: total_supply * 5 / 100, // 5%
-                price: 0,
-                cliff_months: 6,
-                vesting_months: 12,
-                released: 0,
-            },
-            community_airdrops: AllocationInfo {
-                amount: total_supply * 8 / 100, // 8%
-                price: 0,
-                cliff_months: 0,
-                vesting_months: 0, // Claim-based
-                released: 0,
-            },
-        };
+        token_info.allocations = TokenomicsAllocations::new(total_supply)?;
+        
+        require!(
+            token_info.allocations.total_amount()? <= total_supply,
+            ErrorCode::AllocationsExceedSupply
+        );
 
         Ok(())
     }
 
-    /// Purchase tokens during presale
+    /// Purchase tokens during presale with an accepted stablecoin, priced 1:1 with USD
     pub fn purchase_presale(
         ctx: Context<PurchasePresale>,
         amount: u64,
         allocation_type: u8,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
         let token_info = &mut ctx.accounts.token_info;
         let user_purchase = &mut ctx.accounts.user_purchase;
+        let sale_round = &mut ctx.accounts.sale_round;
+        let user = ctx.accounts.user.key();
+        let current_time = Clock::get()?.unix_timestamp;
         
-        // Validate allocation type and availability
-        let allocation = match allocation_type {
-            0 => &mut token_info.allocations.seed_round,
-            1 => &mut token_info.allocations.public_presale,
-            _ => return Err(ErrorCode::InvalidAllocationType.into()),
-        };
+        let price = validate_purchase(
+            token_info,
+            sale_round,
+            user_purchase,
+            user,
+            amount,
+            allocation_type,
+            &proof,
+            current_time,
+        )?;
         
+        // Payment must be in an accepted mint
+        let payment_mint = &ctx.accounts.payment_mint;
         require!(
-            allocation.released + amount <= allocation.amount,
-            ErrorCode::InsufficientAllocation
+            token_info
+                .accepted_payments
+                .iter()
+                .any(|payment| payment.mint == payment_mint.key()),
+            ErrorCode::UnsupportedPaymentMint
         );
         
-        // Calculate payment required
-        let payment_required = amount * allocation.price / 1_000_000_000; // Convert from micro-dollars
+        // Calculate payment required in the payment mint's base units
+        let payment_required = calculate_payment(
+            amount,
+            price,
+            token_info.decimals,
+            payment_mint.decimals,
+        )?;
         
-        // Transfer USDC payment
+        // Escrow payment until the round finalizes
         let cpi_accounts = Transfer {
-            from: ctx.accounts.user_usdc.to_account_info(),
-            to: ctx.accounts.treasury_usdc.to_account_info(),
+            from: ctx.accounts.user_payment_account.to_account_info(),
+            to: ctx.accounts.round_escrow.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, payment_required)?;
         
-        // Update purchase record
-        user_purchase.user = ctx.accounts.user.key();
-        user_purchase.allocation_type = allocation_type;
-        user_purchase.amount_purchased += amount;
-        user_purchase.total_paid += payment_required;
-        user_purchase.last_purchase = Clock::get()?.unix_timestamp;
-        
-        // Update allocation
-        allocation.released += amount;
-        token_info.circulating_supply += amount;
+        record_purchase(
+            token_info,
+            sale_round,
+            user_purchase,
+            user,
+            amount,
+            allocation_type,
+            price,
+            payment_mint.key(),
+            payment_required,
+            current_time,
+        )?;
         
         emit!(TokenPurchaseEvent {
-            user: ctx.accounts.user.key(),
+            user,
             amount,
-            price: allocation.price,
+            price,
             allocation_type,
-            timestamp: Clock::get()?.unix_timestamp,
+            payment_asset: payment_mint.key(),
+            payment_amount: payment_required,
+            oracle_price: 0, // Stablecoins are not oracle-priced
+            oracle_expo: 0,
+            max_payment: payment_required,
+            timestamp: current_time,
         });
 
         Ok(())
     }
 
-    /// Claim vested tokens
-    pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
+    /// Purchase tokens during presale with SOL, priced through the configured oracle.
+    /// `max_lamports` bounds the payment against oracle movement.
+    pub fn purchase_presale_sol(
+        ctx: Context<PurchasePresaleSol>,
+        amount: u64,
+        allocation_type: u8,
+        proof: Vec<[u8; 32]>,
+        max_lamports: u64,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
         let user_purchase = &mut ctx.accounts.user_purchase;
-        let token_info = &ctx.accounts.token_info;
+        let sale_round = &mut ctx.accounts.sale_round;
+        let user = ctx.accounts.user.key();
+        let current_time = Clock::get()?.unix_timestamp;
         
-        let claimable_amount = calculate_vested_amount(
-            user_purchase,
+        require!(
+            token_info.sol_payment.treasury != Pubkey::default(),
+            ErrorCode::UnsupportedPaymentMint
+        );
+        
+        let price = validate_purchase(
             token_info,
-            Clock::get()?.unix_timestamp,
+            sale_round,
+            user_purchase,
+            user,
+            amount,
+            allocation_type,
+            &proof,
+            current_time,
         )?;
         
-        require!(claimable_amount > 0, ErrorCode::NoTokensToCllaim);
+        let oracle_price = read_oracle_price(
+            &ctx.accounts.price_feed.to_account_info(),
+            &token_info.sol_payment.oracle,
+            current_time,
+        )?;
         
-        // Mint tokens to user
-        let seeds = &[
-            b"token_info",
-            &[ctx.bumps.token_info],
-        ];
-        let signer = &[&seeds[..]];
+        let lamports_required = calculate_oracle_payment(
+            amount,
+            price,
+            token_info.decimals,
+            &oracle_price,
+            SOL_DECIMALS,
+        )?;
+        require!(lamports_required <= max_lamports, ErrorCode::SlippageExceeded);
         
-        let cpi_accounts = token::MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.token_info.to_account_info(),
+        // Escrow SOL in the sale round account until the round finalizes
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.user.to_account_info(),
+            to: sale_round.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::mint_to(cpi_ctx, claimable_amount)?;
+        let cpi_program = ctx.accounts.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        system_program::transfer(cpi_ctx, lamports_required)?;
+        sale_round.sol_escrowed = checked_add(sale_round.sol_escrowed, lamports_required)?;
         
-        user_purchase.amount_claimed += claimable_amount;
-        user_purchase.last_claim = Clock::get()?.unix_timestamp;
+        record_purchase(
+            token_info,
+            sale_round,
+            user_purchase,
+            user,
+            amount,
+            allocation_type,
+            price,
+            native_mint::ID,
+            lamports_required,
+            current_time,
+        )?;
         
-        emit!(TokenClaimEvent {
-            user: ctx.accounts.user.key(),
-            amount: claimable_amount,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit!(TokenPurchaseEvent {
+            user,
+            amount,
+            price,
+            allocation_type,
+            payment_asset: native_mint::ID,
+            payment_amount: lamports_required,
+            oracle_price: oracle_price.price,
+            oracle_expo: oracle_price.expo,
+            max_payment: max_lamports,
+            timestamp: current_time,
         });
 
         Ok(())
     }
-}
 
-#[derive(Accounts)]
-pub struct InitializeToken<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + TokenInfo::LEN,
-        seeds = [b"token_info"],
-        bump
-    )]
-    pub token_info: Account<'info, TokenInfo>,
-    
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    /// Accept SOL for presale payments, priced through `oracle` and paid into `treasury`.
+    /// A default `treasury` disables SOL payments.
+    pub fn set_sol_payment(
+        ctx: Context<SetSolPayment>,
+        treasury: Pubkey,
+        oracle: OracleConfig,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(
+            oracle.max_staleness > 0 && oracle.max_confidence_bps <= 10_000,
+            ErrorCode::InvalidOracleConfig
+        );
+        
+        token_info.sol_payment = SolPaymentConfig { treasury, oracle };
+        
+        emit!(PaymentMintUpdated {
+            mint: native_mint::ID,
+            treasury,
+            accepted: treasury != Pubkey::default(),
+        });
 
-#[derive(Accounts)]
-pub struct PurchasePresale<'info> {
-    #[account(
-        mut,
-        seeds = [b"token_info"],
-        bump
-    )]
-    pub token_info: Account<'info, TokenInfo>,
-    
-    #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + UserPurchase::LEN,
-        seeds = [b"user_purchase", user.key().as_ref()],
-        bump
-    )]
-    pub user_purchase: Account<'info, UserPurchase>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub user_usdc: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub treasury_usdc: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct ClaimVestedTokens<'info> {
-    #[account(
-        seeds = [b"token_info"],
-        bump
-    )]
-    pub token_info: Account<'info, TokenInfo>,
-    
-    #[account(
-        mut,
-        seeds = [b"user_purchase", user.key().as_ref()],
-        bump
-    )]
-    pub user_purchase: Account<'info, UserPurchase>,
-    
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
-}
+    /// Accept a stablecoin mint for presale payments, paid into the given treasury account
+    pub fn add_payment_mint(ctx: Context<AddPaymentMint>) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let payment_mint = ctx.accounts.payment_mint.key();
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(
+            !token_info.accepted_payments.iter().any(|payment| payment.mint == payment_mint),
+            ErrorCode::PaymentMintAlreadyAccepted
+        );
+        require!(
+            token_info.accepted_payments.len() < MAX_PAYMENT_MINTS,
+            ErrorCode::TooManyPaymentMints
+        );
+        
+        token_info.accepted_payments.push(AcceptedPayment {
+            mint: payment_mint,
+            treasury: ctx.accounts.treasury_payment_account.key(),
+        });
+        
+        emit!(PaymentMintUpdated {
+            mint: payment_mint,
+            treasury: ctx.accounts.treasury_payment_account.key(),
+            accepted: true,
+        });
 
-#[account]
-pub struct TokenInfo {
-    pub total_supply: u64,
-    pub circulating_supply: u64,
-    pub decimals: u8,
-    pub authority: Pubkey,
-    pub mint: Pubkey,
-    pub allocations: TokenomicsAllocations,
-}
+        Ok(())
+    }
 
-impl TokenInfo {
-    pub const LEN: usize = 8 + 8 + 1 + 32 + 32 + TokenomicsAllocations::LEN;
-}
+    /// Stop accepting a payment mint
+    pub fn remove_payment_mint(ctx: Context<RemovePaymentMint>, payment_mint: Pubkey) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        let index = token_info
+            .accepted_payments
+            .iter()
+            .position(|payment| payment.mint == payment_mint)
+            .ok_or(ErrorCode::UnsupportedPaymentMint)?;
+        let removed = token_info.accepted_payments.remove(index);
+        
+        emit!(PaymentMintUpdated {
+            mint: removed.mint,
+            treasury: removed.treasury,
+            accepted: false,
+        });
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct TokenomicsAllocations {
-    pub seed_round: AllocationInfo,
-    pub public_presale: AllocationInfo,
-    pub liquidity_pool: AllocationInfo,
-    pub team_advisors: AllocationInfo,
-    pub ecosystem_rewards: AllocationInfo,
-    pub treasury_dev: AllocationInfo,
-    pub strategic_partners: AllocationInfo,
-    pub community_airdrops: AllocationInfo,
-}
+        Ok(())
+    }
 
-impl TokenomicsAllocations {
-    pub const LEN: usize = AllocationInfo::LEN * 8;
-}
+    /// Open the next sale round for a presale allocation (seed or public), either the
+    /// first one or a retry after a failed round. A zero `merkle_root` opens the round to everyone.
+    pub fn create_sale_round(
+        ctx: Context<CreateSaleRound>,
+        allocation_type: u8,
+        params: SaleRoundParams,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let sale_round = &mut ctx.accounts.sale_round;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(
+            matches!(allocation_type, 0 | 1),
+            ErrorCode::InvalidAllocationType
+        );
+        // A new round may follow a failed one, never an open or successful one
+        require!(
+            !token_info.sale_round_open[allocation_type as usize]
+                && !token_info.presale_round_succeeded[allocation_type as usize],
+            ErrorCode::SaleRoundExists
+        );
+        require!(params.start_time < params.end_time, ErrorCode::InvalidRoundConfig);
+        require!(
+            params.min_purchase <= params.max_per_wallet && params.max_per_wallet > 0,
+            ErrorCode::InvalidRoundConfig
+        );
+        
+        let allocation = token_info.allocations.get(allocation_type)?;
+        require!(
+            params.hard_cap > 0 && params.hard_cap <= checked_sub(allocation.amount, allocation.released)?,
+            ErrorCode::InvalidRoundConfig
+        );
+        require!(params.soft_cap <= params.hard_cap, ErrorCode::InvalidRoundConfig);
+        
+        sale_round.allocation_type = allocation_type;
+        sale_round.round_index = token_info.sale_round_count[allocation_type as usize];
+        sale_round.start_time = params.start_time;
+        sale_round.end_time = params.end_time;
+        sale_round.min_purchase = params.min_purchase;
+        sale_round.max_per_wallet = params.max_per_wallet;
+        sale_round.soft_cap = params.soft_cap;
+        sale_round.hard_cap = params.hard_cap;
+        sale_round.merkle_root = params.merkle_root;
+        sale_round.sold = 0;
+        sale_round.sol_escrowed = 0;
+        sale_round.paused = false;
+        sale_round.status = RoundStatus::Active;
+        
+        token_info.sale_round_count[allocation_type as usize] = sale_round
+            .round_index
+            .checked_add(1)
+            .ok_or(CommonError::MathOverflow)?;
+        token_info.sale_round_open[allocation_type as usize] = true;
+        
+        emit!(SaleRoundCreated {
+            allocation_type,
+            round_index: sale_round.round_index,
+            start_time: params.start_time,
+            end_time: params.end_time,
+            soft_cap: params.soft_cap,
+            hard_cap: params.hard_cap,
+            allowlisted: params.merkle_root != [0u8; 32],
+        });
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AllocationInfo {
-    pub amount: u64,
-    pub price: u64, // In micro-dollars
-    pub cliff_months: u8,
-    pub vesting_months: u8,
-    pub released: u64,
-}
+        Ok(())
+    }
 
-impl AllocationInfo {
-    pub const LEN: usize = 8 + 8 + 1 + 1 + 8;
-}
+    /// Pause or resume purchases in a sale round
+    pub fn set_round_paused(ctx: Context<UpdateSaleRound>, paused: bool) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
+        let sale_round = &mut ctx.accounts.sale_round;
+        
+        require!(
+            token_info.roles.holds(token_info.authority, Role::Pauser, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        require!(sale_round.status == RoundStatus::Active, ErrorCode::RoundFinalized);
+        
+        sale_round.paused = paused;
+        
+        emit!(SaleRoundPauseUpdated {
+            allocation_type: sale_round.allocation_type,
+            paused,
+        });
 
-#[account]
-pub struct UserPurchase {
+        Ok(())
+    }
+
+    /// Close a sale round permanently. The round succeeds once the soft cap is met;
+    /// otherwise it fails after `end_time` and buyers can refund.
+    pub fn finalize_round(ctx: Context<UpdateSaleRound>) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let sale_round = &mut ctx.accounts.sale_round;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            token_info.roles.holds(token_info.authority, Role::Operator, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        require!(sale_round.status == RoundStatus::Active, ErrorCode::RoundFinalized);
+        
+        let succeeded = sale_round.sold >= sale_round.soft_cap;
+        require!(
+            succeeded || current_time >= sale_round.end_time,
+            ErrorCode::RoundStillOpen
+        );
+        
+        sale_round.status = if succeeded {
+            RoundStatus::Succeeded
+        } else {
+            RoundStatus::Failed
+        };
+        token_info.presale_round_succeeded[sale_round.allocation_type as usize] = succeeded;
+        token_info.sale_round_open[sale_round.allocation_type as usize] = false;
+        
+        emit!(SaleRoundFinalized {
+            allocation_type: sale_round.allocation_type,
+            round_index: sale_round.round_index,
+            sold: sale_round.sold,
+            succeeded,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Release a successful round's escrowed stablecoin proceeds to the registered treasury
+    pub fn withdraw_round_proceeds(ctx: Context<WithdrawRoundProceeds>) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
+        let sale_round = &ctx.accounts.sale_round;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(sale_round.status == RoundStatus::Succeeded, ErrorCode::RoundNotSucceeded);
+        
+        let accepted = token_info
+            .accepted_payments
+            .iter()
+            .find(|payment| payment.mint == ctx.accounts.payment_mint.key())
+            .ok_or(ErrorCode::UnsupportedPaymentMint)?;
+        require!(
+            ctx.accounts.treasury_payment_account.key() == accepted.treasury,
+            ErrorCode::InvalidTreasury
+        );
+        
+        let amount = ctx.accounts.round_escrow.amount;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let allocation_seed = sale_round.allocation_type.to_le_bytes();
+        let round_seed = sale_round.round_index.to_le_bytes();
+        let seeds = &[
+            seeds::SALE_ROUND,
+            allocation_seed.as_ref(),
+            round_seed.as_ref(),
+            &[*ctx.bumps.get("sale_round").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.round_escrow.to_account_info(),
+            to: ctx.accounts.treasury_payment_account.to_account_info(),
+            authority: ctx.accounts.sale_round.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+        
+        emit!(RoundProceedsWithdrawn {
+            allocation_type: sale_round.allocation_type,
+            payment_asset: ctx.accounts.payment_mint.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Release a successful round's escrowed SOL to the SOL treasury
+    pub fn withdraw_round_proceeds_sol(ctx: Context<WithdrawRoundProceedsSol>) -> Result<()> {
+        let sale_round = &mut ctx.accounts.sale_round;
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(sale_round.status == RoundStatus::Succeeded, ErrorCode::RoundNotSucceeded);
+        
+        let amount = sale_round.sol_escrowed;
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        sale_round.sol_escrowed = 0;
+        transfer_lamports(
+            &sale_round.to_account_info(),
+            &ctx.accounts.sol_treasury.to_account_info(),
+            amount,
+        )?;
+        
+        emit!(RoundProceedsWithdrawn {
+            allocation_type: sale_round.allocation_type,
+            payment_asset: native_mint::ID,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Refund a stablecoin payment from a failed round; the lot returns to the allocation
+    pub fn refund_presale(ctx: Context<RefundPresale>) -> Result<()> {
+        let sale_round = &ctx.accounts.sale_round;
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        let payment_mint = ctx.accounts.payment_mint.key();
+        
+        require!(sale_round.status == RoundStatus::Failed, ErrorCode::RoundNotFailed);
+        
+        let amount = take_refund(user_purchase, payment_mint)?;
+        
+        let allocation_seed = sale_round.allocation_type.to_le_bytes();
+        let round_seed = sale_round.round_index.to_le_bytes();
+        let seeds = &[
+            seeds::SALE_ROUND,
+            allocation_seed.as_ref(),
+            round_seed.as_ref(),
+            &[*ctx.bumps.get("sale_round").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.round_escrow.to_account_info(),
+            to: ctx.accounts.user_payment_account.to_account_info(),
+            authority: ctx.accounts.sale_round.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+        
+        return_refunded_lot(&mut ctx.accounts.token_info, user_purchase)?;
+        
+        emit!(PresaleRefunded {
+            user: user_purchase.user,
+            allocation_type: user_purchase.allocation_type,
+            payment_asset: payment_mint,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Refund a SOL payment from a failed round; the lot returns to the allocation
+    pub fn refund_presale_sol(ctx: Context<RefundPresaleSol>) -> Result<()> {
+        let sale_round = &mut ctx.accounts.sale_round;
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        
+        require!(sale_round.status == RoundStatus::Failed, ErrorCode::RoundNotFailed);
+        
+        let amount = take_refund(user_purchase, native_mint::ID)?;
+        
+        sale_round.sol_escrowed = checked_sub(sale_round.sol_escrowed, amount)?;
+        transfer_lamports(
+            &sale_round.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            amount,
+        )?;
+        
+        return_refunded_lot(&mut ctx.accounts.token_info, user_purchase)?;
+        
+        emit!(PresaleRefunded {
+            user: user_purchase.user,
+            allocation_type: user_purchase.allocation_type,
+            payment_asset: native_mint::ID,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Set the token generation event timestamp that anchors every vesting schedule.
+    /// Can be moved until TGE is reached, after which it is fixed.
+    pub fn set_tge_timestamp(ctx: Context<SetTgeTimestamp>, tge_timestamp: i64) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(
+            token_info.tge_timestamp == 0 || current_time < token_info.tge_timestamp,
+            ErrorCode::TgeAlreadyReached
+        );
+        require!(tge_timestamp >= current_time, ErrorCode::InvalidTgeTimestamp);
+        
+        token_info.tge_timestamp = tge_timestamp;
+        
+        emit!(TgeTimestampSet {
+            tge_timestamp,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Register the governance authority allowed to revoke grants alongside the token authority
+    pub fn set_governance(ctx: Context<SetGovernance>, governance: Pubkey) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        token_info.governance = governance;
+
+        Ok(())
+    }
+
+    /// Claim vested tokens
+    pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        let token_info = &ctx.accounts.token_info;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!token_info.paused.claims, ErrorCode::ClaimsPaused);
+        require!(
+            token_info.tge_timestamp > 0 && current_time >= token_info.tge_timestamp,
+            ErrorCode::TgeNotReached
+        );
+        require!(
+            user_purchase.position_mint == Pubkey::default(),
+            ErrorCode::PositionTokenized
+        );
+        
+        let claimable_amount = calculate_vested_amount(
+            user_purchase,
+            token_info,
+            current_time,
+        )?;
+        
+        require!(claimable_amount > 0, ErrorCode::NoTokensToCllaim);
+        
+        // Mint tokens to user
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            claimable_amount,
+        )?;
+        
+        user_purchase.amount_claimed = checked_add(user_purchase.amount_claimed, claimable_amount)?;
+        user_purchase.last_claim = current_time;
+        
+        emit!(TokenClaimEvent {
+            user: ctx.accounts.user.key(),
+            amount: claimable_amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Claim vested tokens from every lot a wallet holds in one transaction.
+    /// Purchase records and vesting grants are passed as writable `remaining_accounts`.
+    pub fn claim_all_vested_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAllVestedTokens<'info>>,
+    ) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
+        let user = ctx.accounts.user.key();
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!token_info.paused.claims, ErrorCode::ClaimsPaused);
+        require!(
+            token_info.tge_timestamp > 0 && current_time >= token_info.tge_timestamp,
+            ErrorCode::TgeNotReached
+        );
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::NoTokensToCllaim);
+        
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut total_claimable: u64 = 0;
+        
+        for lot_info in ctx.remaining_accounts.iter() {
+            require!(!seen.contains(lot_info.key), ErrorCode::DuplicateLot);
+            seen.push(lot_info.key());
+            
+            let mut lot: Account<'_, UserPurchase> = Account::try_from(lot_info)?;
+            require!(lot.user == user, CommonError::Unauthorized);
+            require!(lot.position_mint == Pubkey::default(), ErrorCode::PositionTokenized);
+            
+            let claimable_amount = calculate_vested_amount(&lot, token_info, current_time)?;
+            if claimable_amount == 0 {
+                continue;
+            }
+            
+            lot.amount_claimed = checked_add(lot.amount_claimed, claimable_amount)?;
+            lot.last_claim = current_time;
+            lot.exit(ctx.program_id)?;
+            
+            total_claimable = checked_add(total_claimable, claimable_amount)?;
+        }
+        
+        require!(total_claimable > 0, ErrorCode::NoTokensToCllaim);
+        
+        // Mint tokens to user
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            total_claimable,
+        )?;
+        
+        emit!(TokenClaimEvent {
+            user,
+            amount: total_claimable,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Grant vested tokens from a team, ecosystem or strategic partner allocation.
+    /// The beneficiary claims through `claim_vested_tokens` like a presale buyer.
+    pub fn create_vesting_grant(
+        ctx: Context<CreateVestingGrant>,
+        allocation_type: u8,
+        amount: u64,
+        start_timestamp: i64,
+        revocable: bool,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let grant = &mut ctx.accounts.grant;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        // Presale allocations are sold, not granted
+        require!(
+            matches!(allocation_type, 3 | 4 | 6),
+            ErrorCode::InvalidAllocationType
+        );
+        
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        reserve_allocation(token_info, allocation_type, amount)?;
+        
+        grant.user = ctx.accounts.beneficiary.key();
+        grant.allocation_type = allocation_type;
+        grant.amount_purchased = amount;
+        grant.amount_claimed = 0;
+        grant.total_paid = 0;
+        grant.vesting_start = start_timestamp; // Vesting clock starts here (0 = at TGE)
+        grant.last_purchase = Clock::get()?.unix_timestamp;
+        grant.last_claim = 0;
+        grant.revocable = revocable;
+        grant.revoked = false;
+        grant.payments = Vec::new();
+        grant.position_mint = Pubkey::default();
+        grant.round_index = 0;
+        
+        emit!(VestingGrantCreated {
+            beneficiary: grant.user,
+            allocation_type,
+            amount,
+            start_timestamp,
+            revocable,
+            timestamp: grant.last_purchase,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a revocable vesting grant. Tokens vested so far stay claimable by the
+    /// beneficiary; the unvested remainder returns to the allocation.
+    pub fn revoke_grant(ctx: Context<RevokeGrant>) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let grant = &mut ctx.accounts.grant;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            is_authority_or_governance(token_info, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        require!(grant.revocable, ErrorCode::GrantNotRevocable);
+        require!(!grant.revoked, ErrorCode::GrantAlreadyRevoked);
+        
+        let (vested_amount, clawed_back) = revoke_vesting_grant(token_info, grant, current_time)?;
+        
+        emit!(GrantRevoked {
+            beneficiary: grant.user,
+            allocation_type: grant.allocation_type,
+            vested_amount,
+            clawed_back,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Open an airdrop campaign over a Merkle root of `(wallet, amount)` leaves.
+    /// `total_amount` is reserved from the community airdrop allocation.
+    pub fn create_airdrop_campaign(
+        ctx: Context<CreateAirdropCampaign>,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        claim_deadline: i64,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let campaign = &mut ctx.accounts.campaign;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(total_amount > 0, ErrorCode::InvalidAmount);
+        require!(claim_deadline > current_time, ErrorCode::InvalidAirdropDeadline);
+        
+        reserve_allocation(token_info, AIRDROP_ALLOCATION, total_amount)?;
+        
+        campaign.id = token_info.airdrop_campaign_count;
+        campaign.merkle_root = merkle_root;
+        campaign.total_amount = total_amount;
+        campaign.claimed_amount = 0;
+        campaign.claim_deadline = claim_deadline;
+        campaign.swept = false;
+        
+        token_info.airdrop_campaign_count = checked_add(token_info.airdrop_campaign_count, 1)?;
+        
+        emit!(AirdropCampaignCreated {
+            campaign_id: campaign.id,
+            total_amount,
+            claim_deadline,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Claim an airdrop with a Merkle proof of `(wallet, amount)`
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let claim_record = &mut ctx.accounts.claim_record;
+        let user = ctx.accounts.user.key();
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!ctx.accounts.token_info.paused.airdrops, ErrorCode::AirdropsPaused);
+        require!(
+            ctx.accounts.token_info.tge_timestamp > 0
+                && current_time >= ctx.accounts.token_info.tge_timestamp,
+            ErrorCode::TgeNotReached
+        );
+        require!(current_time <= campaign.claim_deadline, ErrorCode::AirdropExpired);
+        
+        let leaf = keccak::hashv(&[user.as_ref(), &amount.to_le_bytes()]).0;
+        require!(
+            verify_merkle_proof(&proof, campaign.merkle_root, leaf),
+            ErrorCode::InvalidAirdropProof
+        );
+        require!(
+            checked_add(campaign.claimed_amount, amount)? <= campaign.total_amount,
+            ErrorCode::InsufficientAllocation
+        );
+        
+        // Mint tokens to user
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.user_token_account,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            amount,
+        )?;
+        
+        campaign.claimed_amount = checked_add(campaign.claimed_amount, amount)?;
+        claim_record.campaign = campaign.key();
+        claim_record.user = user;
+        claim_record.amount = amount;
+        claim_record.claimed_at = current_time;
+        
+        emit!(AirdropClaimed {
+            campaign_id: campaign.id,
+            user,
+            amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Return a campaign's unclaimed tokens to the allocation after its deadline
+    pub fn sweep_airdrop(ctx: Context<SweepAirdrop>) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let campaign = &mut ctx.accounts.campaign;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(current_time > campaign.claim_deadline, ErrorCode::AirdropStillActive);
+        require!(!campaign.swept, ErrorCode::AirdropAlreadySwept);
+        
+        let unclaimed = checked_sub(campaign.total_amount, campaign.claimed_amount)?;
+        let allocation = token_info.allocations.get_mut(AIRDROP_ALLOCATION)?;
+        allocation.released = checked_sub(allocation.released, unclaimed)?;
+        campaign.swept = true;
+        
+        emit!(AirdropSwept {
+            campaign_id: campaign.id,
+            unclaimed,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Define a named treasury tranche, reserved from the treasury & development allocation
+    /// and released to `recipient` only when the milestone is attested
+    pub fn define_milestone(
+        ctx: Context<DefineMilestone>,
+        name: String,
+        amount: u64,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let milestone = &mut ctx.accounts.milestone;
+        
+        require!(
+            is_authority_or_governance(token_info, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        require!(
+            !name.is_empty() && name.len() <= MAX_MILESTONE_NAME_LEN,
+            ErrorCode::InvalidMilestoneName
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        reserve_allocation(token_info, TREASURY_ALLOCATION, amount)?;
+        
+        milestone.id = token_info.milestone_count;
+        milestone.name = name;
+        milestone.amount = amount;
+        milestone.recipient = recipient;
+        milestone.met = false;
+        milestone.met_at = 0;
+        milestone.attested_by = Pubkey::default();
+        
+        token_info.milestone_count = checked_add(token_info.milestone_count, 1)?;
+        
+        emit!(MilestoneDefined {
+            milestone_id: milestone.id,
+            name: milestone.name.clone(),
+            amount,
+            recipient,
+        });
+
+        Ok(())
+    }
+
+    /// Attest a milestone as met and mint its tranche to the recipient.
+    /// Governance attests by executing a proposal that calls this instruction.
+    pub fn attest_milestone(ctx: Context<AttestMilestone>) -> Result<()> {
+        let milestone = &mut ctx.accounts.milestone;
+        let attester = ctx.accounts.authority.key();
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            is_authority_or_governance(&ctx.accounts.token_info, attester),
+            CommonError::Unauthorized
+        );
+        require!(!milestone.met, ErrorCode::MilestoneAlreadyMet);
+        
+        // Mint the tranche to the recipient
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.recipient_token_account,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            milestone.amount,
+        )?;
+        
+        milestone.met = true;
+        milestone.met_at = current_time;
+        milestone.attested_by = attester;
+        
+        emit!(MilestoneAttested {
+            milestone_id: milestone.id,
+            amount: milestone.amount,
+            attested_by: attester,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Register the constant-product AMM used for liquidity bootstrapping
+    pub fn set_amm_program(ctx: Context<SetAmmProgram>, amm_program: Pubkey) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        token_info.amm_program = amm_program;
+
+        Ok(())
+    }
+
+    /// Seed an AMM pool from the liquidity pool allocation, paired with a succeeded round's
+    /// raised stablecoins at the listing price. LP tokens stay locked in a program account
+    /// for `lock_duration` seconds.
+    pub fn bootstrap_liquidity(
+        ctx: Context<BootstrapLiquidity>,
+        arkly_amount: u64,
+        min_lp_tokens: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(
+            token_info.amm_program != Pubkey::default()
+                && ctx.accounts.amm_program.key() == token_info.amm_program,
+            ErrorCode::InvalidAmmProgram
+        );
+        require!(
+            ctx.accounts.sale_round.status == RoundStatus::Succeeded,
+            ErrorCode::RoundNotSucceeded
+        );
+        require!(arkly_amount > 0 && lock_duration > 0, ErrorCode::InvalidAmount);
+        require!(
+            token_info.tge_timestamp > 0 && current_time >= token_info.tge_timestamp,
+            ErrorCode::TgeNotReached
+        );
+        
+        // The allocation is released on its own schedule from TGE
+        let allocation = token_info.allocations.get(LIQUIDITY_ALLOCATION)?;
+        let unlocked = vested_share(
+            allocation.amount,
+            allocation,
+            current_time - token_info.tge_timestamp,
+        )?;
+        require!(
+            checked_add(allocation.released, arkly_amount)? <= unlocked,
+            ErrorCode::LiquidityNotVested
+        );
+        
+        // Pair at the listing price
+        let listing_price = allocation.price;
+        let quote_amount = calculate_payment(
+            arkly_amount,
+            listing_price,
+            token_info.decimals,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+        require!(
+            ctx.accounts.round_escrow.amount >= quote_amount,
+            ErrorCode::InsufficientProceeds
+        );
+        
+        reserve_allocation(token_info, LIQUIDITY_ALLOCATION, arkly_amount)?;
+        
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.liquidity_arkly,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            arkly_amount,
+        )?;
+        
+        // Move the raised stablecoins out of the round escrow
+        let allocation_seed = ctx.accounts.sale_round.allocation_type.to_le_bytes();
+        let round_seed = ctx.accounts.sale_round.round_index.to_le_bytes();
+        let round_seeds = &[
+            seeds::SALE_ROUND,
+            allocation_seed.as_ref(),
+            round_seed.as_ref(),
+            &[*ctx.bumps.get("sale_round").unwrap()],
+        ];
+        let round_signer = &[&round_seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.round_escrow.to_account_info(),
+            to: ctx.accounts.liquidity_quote.to_account_info(),
+            authority: ctx.accounts.sale_round.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, round_signer);
+        token::transfer(cpi_ctx, quote_amount)?;
+        
+        // Deposit both sides into the AMM, signed by the token info PDA
+        let lp_before = ctx.accounts.lp_lock_vault.amount;
+        
+        let mut args = arkly_amount.to_le_bytes().to_vec();
+        args.extend_from_slice(&quote_amount.to_le_bytes());
+        args.extend_from_slice(&min_lp_tokens.to_le_bytes());
+        let instruction = amm_instruction(
+            ctx.accounts.amm_program.key(),
+            "add_liquidity",
+            vec![
+                AccountMeta::new_readonly(ctx.accounts.amm_pool.key(), false),
+                AccountMeta::new(ctx.accounts.amm_vault_arkly.key(), false),
+                AccountMeta::new(ctx.accounts.amm_vault_quote.key(), false),
+                AccountMeta::new(ctx.accounts.lp_mint.key(), false),
+                AccountMeta::new(ctx.accounts.liquidity_arkly.key(), false),
+                AccountMeta::new(ctx.accounts.liquidity_quote.key(), false),
+                AccountMeta::new(ctx.accounts.lp_lock_vault.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_info.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            args,
+        );
+        invoke_signed(
+            &instruction,
+            &[
+                ctx.accounts.amm_pool.to_account_info(),
+                ctx.accounts.amm_vault_arkly.to_account_info(),
+                ctx.accounts.amm_vault_quote.to_account_info(),
+                ctx.accounts.lp_mint.to_account_info(),
+                ctx.accounts.liquidity_arkly.to_account_info(),
+                ctx.accounts.liquidity_quote.to_account_info(),
+                ctx.accounts.lp_lock_vault.to_account_info(),
+                ctx.accounts.token_info.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.amm_program.to_account_info(),
+            ],
+            &[&[seeds::TOKEN_INFO, &[*ctx.bumps.get("token_info").unwrap()]]],
+        )?;
+        
+        ctx.accounts.lp_lock_vault.reload()?;
+        let lp_received = checked_sub(ctx.accounts.lp_lock_vault.amount, lp_before)?;
+        require!(lp_received >= min_lp_tokens, ErrorCode::SlippageExceeded);
+        
+        let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+        liquidity_lock.pool = ctx.accounts.amm_pool.key();
+        liquidity_lock.lp_mint = ctx.accounts.lp_mint.key();
+        liquidity_lock.lp_amount = checked_add(liquidity_lock.lp_amount, lp_received)?;
+        let unlock_at = current_time
+            .checked_add(lock_duration)
+            .ok_or(CommonError::MathOverflow)?;
+        liquidity_lock.unlock_at = liquidity_lock.unlock_at.max(unlock_at);
+        
+        emit!(LiquidityBootstrapped {
+            pool: liquidity_lock.pool,
+            arkly_amount,
+            quote_mint: ctx.accounts.quote_mint.key(),
+            quote_amount,
+            lp_amount: lp_received,
+            unlock_at: liquidity_lock.unlock_at,
+        });
+
+        Ok(())
+    }
+
+    /// Release locked LP tokens after the lock expires
+    pub fn withdraw_locked_lp(ctx: Context<WithdrawLockedLp>, amount: u64) -> Result<()> {
+        let liquidity_lock = &mut ctx.accounts.liquidity_lock;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_info.authority,
+            CommonError::Unauthorized
+        );
+        require!(current_time >= liquidity_lock.unlock_at, ErrorCode::LiquidityLocked);
+        require!(
+            amount > 0 && amount <= liquidity_lock.lp_amount,
+            ErrorCode::InvalidAmount
+        );
+        
+        let seeds = &[
+            seeds::TOKEN_INFO,
+            &[*ctx.bumps.get("token_info").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.lp_lock_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.token_info.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+        
+        liquidity_lock.lp_amount = checked_sub(liquidity_lock.lp_amount, amount)?;
+        
+        emit!(LockedLpWithdrawn {
+            pool: liquidity_lock.pool,
+            amount,
+            destination: ctx.accounts.destination.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Configure buybacks for a stablecoin revenue vault against an AMM pool
+    pub fn configure_buyback(ctx: Context<ConfigureBuyback>, params: BuybackParams) -> Result<()> {
+        require!(
+            is_authority_or_governance(&ctx.accounts.token_info, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        require!(
+            params.max_amount_per_buyback > 0 && params.max_price > 0 && params.min_interval >= 0,
+            ErrorCode::InvalidBuybackConfig
+        );
+        
+        let buyback_config = &mut ctx.accounts.buyback_config;
+        buyback_config.quote_mint = ctx.accounts.quote_mint.key();
+        buyback_config.pool = params.pool;
+        buyback_config.permissionless = params.permissionless;
+        buyback_config.max_amount_per_buyback = params.max_amount_per_buyback;
+        buyback_config.min_interval = params.min_interval;
+        buyback_config.max_price = params.max_price;
+
+        Ok(())
+    }
+
+    /// Deposit protocol revenue into the buyback revenue vault
+    pub fn deposit_revenue(ctx: Context<DepositRevenue>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.revenue_vault.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        emit!(RevenueDeposited {
+            quote_mint: ctx.accounts.revenue_vault.mint,
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Swap revenue for ARKLY through the registered AMM and burn what was bought.
+    /// The output must cover both `min_arkly_out` and the configured maximum price.
+    pub fn execute_buyback(
+        ctx: Context<ExecuteBuyback>,
+        amount_in: u64,
+        min_arkly_out: u64,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let token_info = &ctx.accounts.token_info;
+        let buyback_config = &ctx.accounts.buyback_config;
+        
+        let caller = ctx.accounts.caller.key();
+        require!(
+            buyback_config.permissionless
+                || is_authority_or_governance(token_info, caller)
+                || token_info.roles.holds(token_info.authority, Role::Operator, caller),
+            CommonError::Unauthorized
+        );
+        require!(
+            token_info.amm_program != Pubkey::default()
+                && ctx.accounts.amm_program.key() == token_info.amm_program,
+            ErrorCode::InvalidAmmProgram
+        );
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        require!(
+            amount_in <= buyback_config.max_amount_per_buyback,
+            ErrorCode::BuybackLimitExceeded
+        );
+        require!(
+            ctx.accounts.revenue_vault.amount >= amount_in,
+            ErrorCode::InsufficientRevenue
+        );
+        require!(
+            buyback_config.last_buyback == 0
+                || current_time
+                    >= buyback_config
+                        .last_buyback
+                        .checked_add(buyback_config.min_interval)
+                        .ok_or(CommonError::MathOverflow)?,
+            ErrorCode::BuybackCooldown
+        );
+        
+        // Never pay more than the configured price, whoever the caller is
+        let price_floor = calculate_tokens_for_payment(
+            amount_in,
+            buyback_config.max_price,
+            token_info.decimals,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+        let min_out = min_arkly_out.max(price_floor);
+        
+        let arkly_before = ctx.accounts.buyback_arkly.amount;
+        
+        let mut args = amount_in.to_le_bytes().to_vec();
+        args.extend_from_slice(&min_out.to_le_bytes());
+        args.push(0); // a_to_b = false: stablecoin in, ARKLY out
+        let instruction = amm_instruction(
+            ctx.accounts.amm_program.key(),
+            "swap",
+            vec![
+                AccountMeta::new_readonly(ctx.accounts.amm_pool.key(), false),
+                AccountMeta::new(ctx.accounts.amm_vault_arkly.key(), false),
+                AccountMeta::new(ctx.accounts.amm_vault_quote.key(), false),
+                AccountMeta::new(ctx.accounts.revenue_vault.key(), false),
+                AccountMeta::new(ctx.accounts.buyback_arkly.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.token_info.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            args,
+        );
+        invoke_signed(
+            &instruction,
+            &[
+                ctx.accounts.amm_pool.to_account_info(),
+                ctx.accounts.amm_vault_arkly.to_account_info(),
+                ctx.accounts.amm_vault_quote.to_account_info(),
+                ctx.accounts.revenue_vault.to_account_info(),
+                ctx.accounts.buyback_arkly.to_account_info(),
+                ctx.accounts.token_info.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.amm_program.to_account_info(),
+            ],
+            &[&[seeds::TOKEN_INFO, &[*ctx.bumps.get("token_info").unwrap()]]],
+        )?;
+        
+        ctx.accounts.buyback_arkly.reload()?;
+        let arkly_bought = checked_sub(ctx.accounts.buyback_arkly.amount, arkly_before)?;
+        require!(arkly_bought >= min_out, ErrorCode::SlippageExceeded);
+        
+        let seeds = &[
+            seeds::TOKEN_INFO,
+            &[*ctx.bumps.get("token_info").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.mint.to_account_info(),
+            from: ctx.accounts.buyback_arkly.to_account_info(),
+            authority: ctx.accounts.token_info.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::burn(cpi_ctx, arkly_bought)?;
+        
+        let token_info = &mut ctx.accounts.token_info;
+        token_info.circulating_supply = token_info.circulating_supply.saturating_sub(arkly_bought);
+        token_info.total_burned = checked_add(token_info.total_burned, arkly_bought)?;
+        
+        let buyback_config = &mut ctx.accounts.buyback_config;
+        buyback_config.last_buyback = current_time;
+        buyback_config.total_spent = checked_add(buyback_config.total_spent, amount_in)?;
+        
+        emit!(Buyback {
+            caller: ctx.accounts.caller.key(),
+            quote_mint: buyback_config.quote_mint,
+            amount_in,
+            arkly_burned: arkly_bought,
+            total_burned: token_info.total_burned,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Require token authority or governance co-signature on position transfers
+    pub fn set_position_transfer_approval(
+        ctx: Context<SetPositionTransferApproval>,
+        required: bool,
+    ) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            is_authority_or_governance(token_info, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
+        );
+        
+        token_info.position_transfer_approval = required;
+
+        Ok(())
+    }
+
+    /// Turn a presale lot or vesting grant into a non-fungible position token. From then
+    /// on whoever holds the token claims the lot's vesting through `claim_position`.
+    pub fn tokenize_position(ctx: Context<TokenizePosition>) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
+        let user_purchase = &ctx.accounts.user_purchase;
+        
+        require!(
+            user_purchase.position_mint == Pubkey::default(),
+            ErrorCode::PositionTokenized
+        );
+        require!(
+            user_purchase.amount_purchased > user_purchase.amount_claimed,
+            ErrorCode::NoTokensToCllaim
+        );
+        // Unsettled presale lots may still be refunded to the buyer
+        require!(user_purchase.round_succeeded(token_info), ErrorCode::RoundNotSucceeded);
+        
+        let seeds = &[
+            seeds::TOKEN_INFO,
+            &[*ctx.bumps.get("token_info").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        
+        // Mint the single position token, then drop the mint authority so supply stays at one
+        let cpi_accounts = token::MintTo {
+            mint: ctx.accounts.position_mint.to_account_info(),
+            to: ctx.accounts.position_account.to_account_info(),
+            authority: ctx.accounts.token_info.to_account_info(),
+        };
+        token::mint_to(CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer), 1)?;
+        
+        let cpi_accounts = token::SetAuthority {
+            current_authority: ctx.accounts.token_info.to_account_info(),
+            account_or_mint: ctx.accounts.position_mint.to_account_info(),
+        };
+        token::set_authority(
+            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+        
+        // Position accounts stay frozen; they only move through `transfer_position`
+        let cpi_accounts = FreezeAccount {
+            account: ctx.accounts.position_account.to_account_info(),
+            mint: ctx.accounts.position_mint.to_account_info(),
+            authority: ctx.accounts.token_info.to_account_info(),
+        };
+        token::freeze_account(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer))?;
+        
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        user_purchase.position_mint = ctx.accounts.position_mint.key();
+        
+        emit!(PositionTokenized {
+            lot: user_purchase.key(),
+            position_mint: user_purchase.position_mint,
+            owner: ctx.accounts.user.key(),
+            remaining: checked_sub(user_purchase.amount_purchased, user_purchase.amount_claimed)?,
+        });
+
+        Ok(())
+    }
+
+    /// Claim vested tokens from a tokenized lot as the current position holder
+    pub fn claim_position(ctx: Context<ClaimPosition>) -> Result<()> {
+        let user_purchase = &mut ctx.accounts.user_purchase;
+        let token_info = &ctx.accounts.token_info;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        require!(!token_info.paused.claims, ErrorCode::ClaimsPaused);
+        require!(
+            token_info.tge_timestamp > 0 && current_time >= token_info.tge_timestamp,
+            ErrorCode::TgeNotReached
+        );
+        
+        let claimable_amount = calculate_vested_amount(
+            user_purchase,
+            token_info,
+            current_time,
+        )?;
+        
+        require!(claimable_amount > 0, ErrorCode::NoTokensToCllaim);
+        
+        mint_arkly(
+            &mut ctx.accounts.token_info,
+            &ctx.accounts.mint,
+            &ctx.accounts.holder_token_account,
+            &ctx.accounts.token_program,
+            *ctx.bumps.get("token_info").unwrap(),
+            claimable_amount,
+        )?;
+        
+        user_purchase.amount_claimed = checked_add(user_purchase.amount_claimed, claimable_amount)?;
+        user_purchase.last_claim = current_time;
+        
+        emit!(TokenClaimEvent {
+            user: ctx.accounts.holder.key(),
+            amount: claimable_amount,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Move a position token to another account. Needs an `approver` (token authority,
+    /// governance or the compliance role) when transfer approval is required.
+    pub fn transfer_position(ctx: Context<TransferPosition>) -> Result<()> {
+        let token_info = &ctx.accounts.token_info;
+        
+        if token_info.position_transfer_approval {
+            let approver = ctx
+                .accounts
+                .approver
+                .as_ref()
+                .ok_or(ErrorCode::TransferNotApproved)?;
+            require!(
+                is_authority_or_governance(token_info, approver.key())
+                    || token_info.roles.holds(token_info.authority, Role::Compliance, approver.key()),
+                ErrorCode::TransferNotApproved
+            );
+        }
+        
+        let seeds = &[
+            seeds::TOKEN_INFO,
+            &[*ctx.bumps.get("token_info").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        
+        for account in [&ctx.accounts.source, &ctx.accounts.destination] {
+            if !account.is_frozen() {
+                continue;
+            }
+            let cpi_accounts = ThawAccount {
+                account: account.to_account_info(),
+                mint: ctx.accounts.position_mint.to_account_info(),
+                authority: ctx.accounts.token_info.to_account_info(),
+            };
+            token::thaw_account(CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer))?;
+        }
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.holder.to_account_info(),
+        };
+        token::transfer(CpiContext::new(cpi_program.clone(), cpi_accounts), 1)?;
+        
+        for account in [&ctx.accounts.source, &ctx.accounts.destination] {
+            let cpi_accounts = FreezeAccount {
+                account: account.to_account_info(),
+                mint: ctx.accounts.position_mint.to_account_info(),
+                authority: ctx.accounts.token_info.to_account_info(),
+            };
+            token::freeze_account(CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts, signer))?;
+        }
+        
+        emit!(PositionTransferred {
+            position_mint: ctx.accounts.position_mint.key(),
+            from: ctx.accounts.holder.key(),
+            to: ctx.accounts.destination.owner,
+            approver: ctx.accounts.approver.as_ref().map(|approver| approver.key()),
+        });
+
+        Ok(())
+    }
+
+    /// Start handing the admin role to `new_authority`, which must accept it.
+    /// Proposing `Pubkey::default()` cancels a pending handover.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        token_info.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            scope: token_info.key(),
+            authority: token_info.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Complete an admin handover as the proposed key. A governance PDA accepts by
+    /// executing a proposal whose instruction bundle calls this instruction.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let new_authority = ctx.accounts.new_authority.key();
+        
+        require!(
+            token_info.pending_authority != Pubkey::default()
+                && new_authority == token_info.pending_authority,
+            CommonError::NotPendingAuthority
+        );
+        
+        let previous_authority = token_info.authority;
+        token_info.authority = new_authority;
+        token_info.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            scope: token_info.key(),
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Assign a delegated role, or clear it with `Pubkey::default()`
+    pub fn set_role(ctx: Context<SetRole>, role: Role, key: Pubkey) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        
+        require!(
+            ctx.accounts.authority.key() == token_info.authority,
+            CommonError::Unauthorized
+        );
+        
+        token_info.roles.set(role, key);
+        
+        emit!(RoleUpdated {
+            scope: token_info.key(),
+            role: role as u8,
+            key,
+        });
+
+        Ok(())
+    }
+
+    /// Pause or resume purchases, vesting claims or airdrop claims
+    pub fn set_pause(ctx: Context<SetPause>, scope: PauseScope, paused: bool) -> Result<()> {
+        let token_info = &mut ctx.accounts.token_info;
+        let signer = ctx.accounts.authority.key();
+        
+        require!(
+            is_authority_or_governance(token_info, signer)
+                || token_info.roles.holds(token_info.authority, Role::Pauser, signer),
+            CommonError::Unauthorized
+        );
+        
+        match scope {
+            PauseScope::Purchases => token_info.paused.purchases = paused,
+            PauseScope::Claims => token_info.paused.claims = paused,
+            PauseScope::Airdrops => token_info.paused.airdrops = paused,
+        }
+        
+        let timestamp = Clock::get()?.unix_timestamp;
+        if paused {
+            emit!(Paused {
+                scope,
+                by: signer,
+                timestamp,
+            });
+        } else {
+            emit!(Unpaused {
+                scope,
+                by: signer,
+                timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Quote the cost of a presale purchase in `payment_mint`, or in lamports for the
+    /// native mint. Round, wallet and allowlist limits are checked at purchase time only.
+    pub fn quote_presale(
+        ctx: Context<QuotePresale>,
+        amount: u64,
+        allocation_type: u8,
+    ) -> Result<PresaleQuote> {
+        let token_info = &ctx.accounts.token_info;
+        let payment_mint = &ctx.accounts.payment_mint;
+        
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            matches!(allocation_type, 0 | 1),
+            ErrorCode::InvalidAllocationType
+        );
+        let price = token_info.allocations.get(allocation_type)?.price;
+        let usd_value = calculate_payment(amount, price, token_info.decimals, PRICE_DECIMALS as u8)?;
+        
+        if payment_mint.key() == native_mint::ID {
+            require!(
+                token_info.sol_payment.treasury != Pubkey::default(),
+                ErrorCode::UnsupportedPaymentMint
+            );
+            let price_feed = ctx
+                .accounts
+                .price_feed
+                .as_ref()
+                .ok_or(ErrorCode::InvalidPriceFeed)?;
+            let oracle_price = read_oracle_price(
+                &price_feed.to_account_info(),
+                &token_info.sol_payment.oracle,
+                Clock::get()?.unix_timestamp,
+            )?;
+            
+            return Ok(PresaleQuote {
+                price,
+                usd_value,
+                payment_amount: calculate_oracle_payment(
+                    amount,
+                    price,
+                    token_info.decimals,
+                    &oracle_price,
+                    SOL_DECIMALS,
+                )?,
+                oracle_price: oracle_price.price,
+                oracle_expo: oracle_price.expo,
+            });
+        }
+        
+        require!(
+            token_info
+                .accepted_payments
+                .iter()
+                .any(|payment| payment.mint == payment_mint.key()),
+            ErrorCode::UnsupportedPaymentMint
+        );
+        
+        Ok(PresaleQuote {
+            price,
+            usd_value,
+            payment_amount: calculate_payment(amount, price, token_info.decimals, payment_mint.decimals)?,
+            oracle_price: 0,
+            oracle_expo: 0,
+        })
+    }
+
+    /// Report a lot's vesting progress at the current time
+    pub fn get_claimable_vested(ctx: Context<GetClaimableVested>) -> Result<VestingQuote> {
+        let token_info = &ctx.accounts.token_info;
+        let user_purchase = &ctx.accounts.user_purchase;
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        let vested = calculate_vested_total(user_purchase, token_info, current_time)?;
+        
+        Ok(VestingQuote {
+            total: user_purchase.amount_purchased,
+            vested,
+            claimed: user_purchase.amount_claimed,
+            claimable: vested.saturating_sub(user_purchase.amount_claimed),
+            timestamp: current_time,
+        })
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(total_supply: u64, decimals: u8)]
+pub struct InitializeToken<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenInfo::LEN,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// Fresh mint controlled solely by the `token_info` PDA
+    #[account(
+        constraint = mint.mint_authority == COption::Some(token_info.key()) @ ErrorCode::InvalidMintAuthority,
+        constraint = mint.freeze_authority.is_none() @ ErrorCode::InvalidMintAuthority,
+        constraint = mint.decimals == decimals @ ErrorCode::InvalidMintDecimals,
+        constraint = mint.supply == 0 @ ErrorCode::MintAlreadyHasSupply
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, allocation_type: u8)]
+pub struct PurchasePresale<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPurchase::LEN,
+        seeds = [seeds::USER_PURCHASE, user.key().as_ref(), allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::SALE_ROUND,
+            allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_payment_account.mint == payment_mint.key() @ ErrorCode::InvalidPaymentAccount,
+        constraint = user_payment_account.owner == user.key() @ ErrorCode::InvalidPaymentAccount
+    )]
+    pub user_payment_account: Account<'info, TokenAccount>,
+    
+    /// Holds the round's proceeds in `payment_mint` until it finalizes
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [seeds::ROUND_ESCROW, sale_round.key().as_ref(), payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = sale_round
+    )]
+    pub round_escrow: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedTokens<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// Either a presale purchase or a vesting grant owned by the signer
+    #[account(
+        mut,
+        has_one = user @ CommonError::Unauthorized
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, allocation_type: u8)]
+pub struct PurchasePresaleSol<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserPurchase::LEN,
+        seeds = [seeds::USER_PURCHASE, user.key().as_ref(), allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::SALE_ROUND,
+            allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// CHECK: Validated against `token_info.sol_payment.oracle` when read
+    pub price_feed: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetSolPayment<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = treasury_payment_account.mint == payment_mint.key() @ ErrorCode::InvalidTreasury
+    )]
+    pub treasury_payment_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemovePaymentMint<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(allocation_type: u8)]
+pub struct CreateSaleRound<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SaleRound::LEN,
+        seeds = [
+            seeds::SALE_ROUND,
+            allocation_type.to_le_bytes().as_ref(),
+            token_info.next_round_index(allocation_type).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSaleRound<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::SALE_ROUND,
+            sale_round.allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRoundProceeds<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        seeds = [
+            seeds::SALE_ROUND,
+            sale_round.allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ROUND_ESCROW, sale_round.key().as_ref(), payment_mint.key().as_ref()],
+        bump
+    )]
+    pub round_escrow: Account<'info, TokenAccount>,
+    
+    /// Must match the treasury registered for `payment_mint`
+    #[account(mut)]
+    pub treasury_payment_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRoundProceedsSol<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::SALE_ROUND,
+            sale_round.allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(
+        mut,
+        constraint = sol_treasury.key() == token_info.sol_payment.treasury @ ErrorCode::InvalidTreasury
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundPresale<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        seeds = [
+            seeds::SALE_ROUND,
+            user_purchase.allocation_type.to_le_bytes().as_ref(),
+            user_purchase.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::USER_PURCHASE, user.key().as_ref(), user_purchase.allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ROUND_ESCROW, sale_round.key().as_ref(), payment_mint.key().as_ref()],
+        bump
+    )]
+    pub round_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_payment_account.mint == payment_mint.key() @ ErrorCode::InvalidPaymentAccount,
+        constraint = user_payment_account.owner == user.key() @ ErrorCode::InvalidPaymentAccount
+    )]
+    pub user_payment_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RefundPresaleSol<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [
+            seeds::SALE_ROUND,
+            user_purchase.allocation_type.to_le_bytes().as_ref(),
+            user_purchase.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Account<'info, SaleRound>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::USER_PURCHASE, user.key().as_ref(), user_purchase.allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTgeTimestamp<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllVestedTokens<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(allocation_type: u8)]
+pub struct CreateVestingGrant<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + UserPurchase::LEN,
+        seeds = [seeds::VESTING_GRANT, beneficiary.key().as_ref(), allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, UserPurchase>,
+    
+    /// CHECK: Only used as the grant owner; never read or written
+    pub beneficiary: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeGrant<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::VESTING_GRANT, grant.user.as_ref(), grant.allocation_type.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, UserPurchase>,
+    
+    /// Token authority or the registered governance authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateAirdropCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AirdropCampaign::LEN,
+        seeds = [seeds::AIRDROP_CAMPAIGN, token_info.airdrop_campaign_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::AIRDROP_CAMPAIGN, campaign.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+    
+    /// Existence of this record marks the wallet as claimed
+    #[account(
+        init,
+        payer = user,
+        space = 8 + AirdropClaim::LEN,
+        seeds = [seeds::AIRDROP_CLAIM, campaign.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub claim_record: Account<'info, AirdropClaim>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepAirdrop<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::AIRDROP_CAMPAIGN, campaign.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, AirdropCampaign>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DefineMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Milestone::LEN,
+        seeds = [seeds::MILESTONE, token_info.milestone_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub milestone: Account<'info, Milestone>,
+    
+    /// Token authority or the registered governance authority
+    pub authority: Signer<'info>,
+    
+    /// Separate from `authority` so a governance PDA can define milestones
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MILESTONE, milestone.id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub milestone: Account<'info, Milestone>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == milestone.recipient @ ErrorCode::InvalidRecipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    /// Token authority or the registered governance authority
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetAmmProgram<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BootstrapLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    
+    /// Succeeded round whose stablecoin proceeds are paired
+    #[account(
+        seeds = [
+            seeds::SALE_ROUND,
+            sale_round.allocation_type.to_le_bytes().as_ref(),
+            sale_round.round_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub sale_round: Box<Account<'info, SaleRound>>,
+    
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ROUND_ESCROW, sale_round.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub round_escrow: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    
+    /// ARKLY side of the deposit, owned by the token info PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [seeds::LIQUIDITY_ARKLY],
+        bump,
+        token::mint = mint,
+        token::authority = token_info
+    )]
+    pub liquidity_arkly: Box<Account<'info, TokenAccount>>,
+    
+    /// Stablecoin side of the deposit, owned by the token info PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [seeds::LIQUIDITY_QUOTE, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = token_info
+    )]
+    pub liquidity_quote: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    /// Receives and holds the LP tokens while locked
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [seeds::LP_LOCK_VAULT, lp_mint.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = token_info
+    )]
+    pub lp_lock_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LiquidityLock::LEN,
+        seeds = [seeds::LIQUIDITY_LOCK, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub liquidity_lock: Box<Account<'info, LiquidityLock>>,
+    
+    /// CHECK: Must match `token_info.amm_program`
+    pub amm_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool state, validated by the AMM
+    pub amm_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool ARKLY vault, validated by the AMM
+    #[account(mut)]
+    pub amm_vault_arkly: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool stablecoin vault, validated by the AMM
+    #[account(mut)]
+    pub amm_vault_quote: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLockedLp<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LIQUIDITY_LOCK, liquidity_lock.lp_mint.as_ref()],
+        bump
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::LP_LOCK_VAULT, liquidity_lock.lp_mint.as_ref()],
+        bump
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = destination.mint == liquidity_lock.lp_mint @ ErrorCode::InvalidRecipient
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureBuyback<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BuybackConfig::LEN,
+        seeds = [seeds::BUYBACK_CONFIG],
+        bump
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+    
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    /// Collects the stablecoin revenue spent on buybacks
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [seeds::REVENUE_VAULT, quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = token_info
+    )]
+    pub revenue_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Token authority or the registered governance authority
+    pub authority: Signer<'info>,
+    
+    /// Separate from `authority` so a governance PDA can configure buybacks
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositRevenue<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::REVENUE_VAULT, revenue_vault.mint.as_ref()],
+        bump
+    )]
+    pub revenue_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub source: Account<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::BUYBACK_CONFIG],
+        bump,
+        has_one = quote_mint @ ErrorCode::UnsupportedPaymentMint
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+    
+    pub quote_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::REVENUE_VAULT, quote_mint.key().as_ref()],
+        bump
+    )]
+    pub revenue_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    
+    /// Receives the bought ARKLY before it is burned
+    #[account(
+        init_if_needed,
+        payer = caller,
+        seeds = [seeds::BUYBACK_ARKLY],
+        bump,
+        token::mint = mint,
+        token::authority = token_info
+    )]
+    pub buyback_arkly: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Must match `token_info.amm_program`
+    pub amm_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Must be the configured pool; pool state is validated by the AMM
+    #[account(constraint = amm_pool.key() == buyback_config.pool @ ErrorCode::InvalidBuybackConfig)]
+    pub amm_pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool ARKLY vault, validated by the AMM
+    #[account(mut)]
+    pub amm_vault_arkly: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool stablecoin vault, validated by the AMM
+    #[account(mut)]
+    pub amm_vault_quote: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPositionTransferApproval<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TokenizePosition<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Box<Account<'info, TokenInfo>>,
+    
+    /// Either a presale purchase or a vesting grant owned by the signer
+    #[account(
+        mut,
+        has_one = user @ CommonError::Unauthorized
+    )]
+    pub user_purchase: Box<Account<'info, UserPurchase>>,
+    
+    #[account(
+        init,
+        payer = user,
+        seeds = [seeds::POSITION_MINT, user_purchase.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = token_info,
+        mint::freeze_authority = token_info
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = user,
+        seeds = [seeds::POSITION_ACCOUNT, position_mint.key().as_ref()],
+        bump,
+        token::mint = position_mint,
+        token::authority = user
+    )]
+    pub position_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPosition<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    #[account(
+        mut,
+        constraint = user_purchase.position_mint == position_account.mint @ ErrorCode::InvalidPosition
+    )]
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    /// Holder's position token account
+    #[account(
+        constraint = position_account.owner == holder.key() @ ErrorCode::InvalidPosition,
+        constraint = position_account.amount == 1 @ ErrorCode::InvalidPosition
+    )]
+    pub position_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = mint.key() == token_info.mint @ ErrorCode::InvalidMint
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    pub holder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TransferPosition<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// Lot the position token represents
+    #[account(
+        seeds = [seeds::POSITION_MINT, user_purchase.key().as_ref()],
+        bump,
+        constraint = user_purchase.position_mint == position_mint.key() @ ErrorCode::InvalidPosition
+    )]
+    pub position_mint: Account<'info, Mint>,
+    
+    pub user_purchase: Account<'info, UserPurchase>,
+    
+    #[account(
+        mut,
+        constraint = source.mint == position_mint.key() @ ErrorCode::InvalidPosition,
+        constraint = source.owner == holder.key() @ ErrorCode::InvalidPosition
+    )]
+    pub source: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = destination.mint == position_mint.key() @ ErrorCode::InvalidPosition,
+        constraint = destination.key() != source.key() @ ErrorCode::InvalidPosition
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub holder: Signer<'info>,
+    
+    /// Token authority, governance or compliance; required when transfer approval is on
+    pub approver: Option<Signer<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// The pending authority; a governance PDA signs through proposal execution
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// Token authority, governance or the pauser role
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePresale<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// An accepted stablecoin, or the native mint to quote in lamports
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// CHECK: Validated against the SOL oracle config; only needed for native mint quotes
+    pub price_feed: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct GetClaimableVested<'info> {
+    #[account(
+        seeds = [seeds::TOKEN_INFO],
+        bump
+    )]
+    pub token_info: Account<'info, TokenInfo>,
+    
+    /// A presale purchase or vesting grant
+    pub user_purchase: Account<'info, UserPurchase>,
+}
+
+#[account]
+pub struct TokenInfo {
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub total_minted: u64, // Never exceeds `total_supply`
+    pub total_burned: u64, // Burned by buybacks; never re-minted
+    pub decimals: u8,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub tge_timestamp: i64, // 0 until scheduled
+    pub governance: Pubkey, // Default until registered
+    pub accepted_payments: Vec<AcceptedPayment>,
+    pub sol_payment: SolPaymentConfig,
+    pub airdrop_campaign_count: u64,
+    pub milestone_count: u64,
+    pub presale_round_succeeded: [bool; 2], // Seed, public; presale lots vest only after success
+    pub sale_round_count: [u32; 2], // Rounds created per presale allocation; the latest is `count - 1`
+    pub sale_round_open: [bool; 2], // Whether the latest round is still active
+    pub amm_program: Pubkey, // Default until registered
+    pub position_transfer_approval: bool,
+    pub pending_authority: Pubkey, // Default unless a handover is in progress
+    pub roles: Roles,
+    pub paused: PauseFlags,
+    pub allocations: TokenomicsAllocations,
+}
+
+impl TokenInfo {
+    /// Index the next sale round for a presale allocation will take
+    pub fn next_round_index(&self, allocation_type: u8) -> u32 {
+        self.sale_round_count
+            .get(allocation_type as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+    
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1 + 32 + 32 + 8 + 32
+        + 4 + AcceptedPayment::LEN * MAX_PAYMENT_MINTS
+        + SolPaymentConfig::LEN
+        + 8 + 8 + 2 + 8 + 2 + 32 + 1 + 32 + Roles::LEN + PauseFlags::LEN
+        + TokenomicsAllocations::LEN;
+}
+
+/// Emergency switches; each blocks one class of user instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PauseFlags {
+    pub purchases: bool,
+    pub claims: bool, // Vesting and position claims
+    pub airdrops: bool,
+}
+
+impl PauseFlags {
+    pub const LEN: usize = 1 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PauseScope {
+    Purchases,
+    Claims,
+    Airdrops,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AcceptedPayment {
+    pub mint: Pubkey,
+    pub treasury: Pubkey, // Token account receiving this mint
+}
+
+impl AcceptedPayment {
+    pub const LEN: usize = 32 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SolPaymentConfig {
+    pub treasury: Pubkey, // Default = SOL not accepted
+    pub oracle: OracleConfig,
+}
+
+impl SolPaymentConfig {
+    pub const LEN: usize = 32 + OracleConfig::LEN;
+}
+
+/// Price feed used to value a non-USD payment asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct OracleConfig {
+    pub kind: OracleKind,
+    pub program: Pubkey, // Expected owner of the feed account
+    pub feed: Pubkey,
+    pub max_staleness: i64, // Seconds
+    pub max_confidence_bps: u16, // Max confidence interval relative to price
+}
+
+impl OracleConfig {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 2;
+}
+
+/// Supported feed account layouts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OracleKind {
+    #[default]
+    Pyth, // Pyth v2 price account (also written by the mock oracle)
+}
+
+/// Price read from an oracle feed: `price * 10^expo` USD per whole asset
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenomicsAllocations {
+    pub seed_round: AllocationInfo,
+    pub public_presale: AllocationInfo,
+    pub liquidity_pool: AllocationInfo,
+    pub team_advisors: AllocationInfo,
+    pub ecosystem_rewards: AllocationInfo,
+    pub treasury_dev: AllocationInfo,
+    pub strategic_partners: AllocationInfo,
+    pub community_airdrops: AllocationInfo,
+}
+
+impl TokenomicsAllocations {
+    pub const LEN: usize = AllocationInfo::LEN * 8;
+    
+    /// The fixed tokenomics split of `total_supply` with each allocation's schedule
+    pub fn new(total_supply: u64) -> Result<Self> {
+        Ok(TokenomicsAllocations {
+            seed_round: AllocationInfo {
+                amount: mul_div_floor(total_supply, 12, 100)?, // 12%
+                price: 83_000_000, // $0.083 in 9-decimal USD
+                tge_unlock_bps: 0,
+                cliff_duration: 6 * MONTH,
+                vesting_duration: 12 * MONTH,
+                released: 0,
+            },
+            public_presale: AllocationInfo {
+                amount: mul_div_floor(total_supply, 75, 1000)?, // 7.5%
+                price: 100_000_000, // $0.10 in 9-decimal USD
+                tge_unlock_bps: 0,
+                cliff_duration: 0,
+                vesting_duration: 0, // 100% at TGE
+                released: 0,
+            },
+            liquidity_pool: AllocationInfo {
+                amount: mul_div_floor(total_supply, 10, 100)?, // 10%
+                price: 150_000_000, // $0.15 listing price
+                tge_unlock_bps: 5_000,
+                cliff_duration: 30 * DAY,
+                vesting_duration: 0, // 50% at TGE, 50% after 30 days
+                released: 0,
+            },
+            team_advisors: AllocationInfo {
+                amount: mul_div_floor(total_supply, 15, 100)?, // 15%
+                price: 0,
+                tge_unlock_bps: 0,
+                cliff_duration: 12 * MONTH,
+                vesting_duration: 24 * MONTH,
+                released: 0,
+            },
+            ecosystem_rewards: AllocationInfo {
+                amount: mul_div_floor(total_supply, 225, 1000)?, // 22.5%
+                price: 0,
+                tge_unlock_bps: 0,
+                cliff_duration: 0,
+                vesting_duration: 36 * MONTH,
+                released: 0,
+            },
+            treasury_dev: AllocationInfo {
+                amount: mul_div_floor(total_supply, 20, 100)?, // 20%
+                price: 0,
+                tge_unlock_bps: 0,
+                cliff_duration: 0,
+                vesting_duration: 0, // Roadmap-based, released through milestones
+                released: 0,
+            },
+            strategic_partners: AllocationInfo {
+                amount: mul_div_floor(total_supply, 5, 100)?, // 5%
+                price: 0,
+                tge_unlock_bps: 0,
+                cliff_duration: 6 * MONTH,
+                vesting_duration: 12 * MONTH,
+                released: 0,
+            },
+            community_airdrops: AllocationInfo {
+                amount: mul_div_floor(total_supply, 8, 100)?, // 8%
+                price: 0,
+                tge_unlock_bps: 0,
+                cliff_duration: 0,
+                vesting_duration: 0, // Claim-based
+                released: 0,
+            },
+        })
+    }
+    
+    fn all(&self) -> [&AllocationInfo; 8] {
+        [
+            &self.seed_round,
+            &self.public_presale,
+            &self.liquidity_pool,
+            &self.team_advisors,
+            &self.ecosystem_rewards,
+            &self.treasury_dev,
+            &self.strategic_partners,
+            &self.community_airdrops,
+        ]
+    }
+    
+    /// Tokens assigned across every allocation
+    pub fn total_amount(&self) -> Result<u64> {
+        self.all()
+            .iter()
+            .try_fold(0u64, |total, allocation| checked_add(total, allocation.amount))
+    }
+    
+    /// Tokens reserved across every allocation
+    pub fn total_released(&self) -> Result<u64> {
+        self.all()
+            .iter()
+            .try_fold(0u64, |total, allocation| checked_add(total, allocation.released))
+    }
+    
+    pub fn get(&self, allocation_type: u8) -> Result<&AllocationInfo> {
+        match allocation_type {
+            0 => Ok(&self.seed_round),
+            1 => Ok(&self.public_presale),
+            2 => Ok(&self.liquidity_pool),
+            3 => Ok(&self.team_advisors),
+            4 => Ok(&self.ecosystem_rewards),
+            5 => Ok(&self.treasury_dev),
+            6 => Ok(&self.strategic_partners),
+            7 => Ok(&self.community_airdrops),
+            _ => Err(ErrorCode::InvalidAllocationType.into()),
+        }
+    }
+    
+    pub fn get_mut(&mut self, allocation_type: u8) -> Result<&mut AllocationInfo> {
+        match allocation_type {
+            0 => Ok(&mut self.seed_round),
+            1 => Ok(&mut self.public_presale),
+            2 => Ok(&mut self.liquidity_pool),
+            3 => Ok(&mut self.team_advisors),
+            4 => Ok(&mut self.ecosystem_rewards),
+            5 => Ok(&mut self.treasury_dev),
+            6 => Ok(&mut self.strategic_partners),
+            7 => Ok(&mut self.community_airdrops),
+            _ => Err(ErrorCode::InvalidAllocationType.into()),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AllocationInfo {
+    pub amount: u64,
+    pub price: u64, // USD per whole token, `PRICE_DECIMALS` decimals
+    pub tge_unlock_bps: u16, // Share unlocked at the vesting start
+    pub cliff_duration: i64, // Seconds after the vesting start
+    pub vesting_duration: i64, // Seconds of linear unlock after the cliff
+    pub released: u64,
+}
+
+impl AllocationInfo {
+    pub const LEN: usize = 8 + 8 + 2 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct SaleRound {
+    pub allocation_type: u8,
+    pub round_index: u32, // Rounds for an allocation are numbered from 0
+    pub start_time: i64,
+    pub end_time: i64,
+    pub min_purchase: u64, // Per purchase
+    pub max_per_wallet: u64,
+    pub soft_cap: u64, // Tokens sold for the round to succeed
+    pub hard_cap: u64,
+    pub sold: u64,
+    pub sol_escrowed: u64, // Lamports held above rent
+    pub merkle_root: [u8; 32], // All zeros = no allowlist
+    pub paused: bool,
+    pub status: RoundStatus,
+}
+
+impl SaleRound {
+    pub const LEN: usize = 1 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RoundStatus {
+    Active,
+    Succeeded,
+    Failed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SaleRoundParams {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub min_purchase: u64,
+    pub max_per_wallet: u64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub merkle_root: [u8; 32],
+}
+
+#[account]
+pub struct UserPurchase {
+    pub user: Pubkey,
+    pub allocation_type: u8,
+    pub amount_purchased: u64,
+    pub amount_claimed: u64,
+    pub total_paid: u64,
+    pub vesting_start: i64, // 0 = vest from TGE
+    pub last_purchase: i64,
+    pub last_claim: i64,
+    pub revocable: bool, // Only grants can be revocable
+    pub revoked: bool,
+    pub payments: Vec<AssetPayment>, // Escrowed presale payments, refundable if the round fails
+    pub position_mint: Pubkey, // Default unless tokenized; the holder then owns the lot
+    pub round_index: u32, // Sale round a presale lot was bought in; 0 for grants
+}
+
+impl UserPurchase {
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1
+        + 4 + AssetPayment::LEN * (MAX_PAYMENT_MINTS + 1)
+        + 32 + 4;
+    
+    /// Presale lots settle with their own round: they vest and can be tokenized only
+    /// if that round is the allocation's successful one. Grants always qualify.
+    pub fn round_succeeded(&self, token_info: &TokenInfo) -> bool {
+        match self.allocation_type {
+            0 | 1 => {
+                let index = self.allocation_type as usize;
+                token_info.presale_round_succeeded[index]
+                    && self.round_index + 1 == token_info.sale_round_count[index]
+            }
+            _ => true,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AssetPayment {
+    pub asset: Pubkey, // Payment mint, or the native mint for SOL
+    pub amount: u64,
+}
+
+impl AssetPayment {
+    pub const LEN: usize = 32 + 8;
+}
+
+#[account]
+pub struct AirdropCampaign {
+    pub id: u64,
+    pub merkle_root: [u8; 32], // Leaves are keccak(wallet, amount as u64 le)
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub claim_deadline: i64,
+    pub swept: bool,
+}
+
+impl AirdropCampaign {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct AirdropClaim {
+    pub campaign: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+}
+
+impl AirdropClaim {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct Milestone {
+    pub id: u64,
+    pub name: String,
+    pub amount: u64,
+    pub recipient: Pubkey, // ARKLY token account receiving the tranche
+    pub met: bool,
+    pub met_at: i64,
+    pub attested_by: Pubkey,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 4 + MAX_MILESTONE_NAME_LEN + 8 + 32 + 1 + 8 + 32;
+}
+
+#[account]
+pub struct BuybackConfig {
+    pub quote_mint: Pubkey,
+    pub pool: Pubkey,
+    pub permissionless: bool,
+    pub max_amount_per_buyback: u64,
+    pub min_interval: i64,
+    pub max_price: u64, // USD per whole token, `PRICE_DECIMALS` decimals
+    pub last_buyback: i64,
+    pub total_spent: u64,
+}
+
+impl BuybackConfig {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8 + 8 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BuybackParams {
+    pub pool: Pubkey,
+    pub permissionless: bool,
+    pub max_amount_per_buyback: u64,
+    pub min_interval: i64,
+    pub max_price: u64,
+}
+
+/// Returned by `quote_presale`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PresaleQuote {
+    pub price: u64, // USD per whole token, `PRICE_DECIMALS` decimals
+    pub usd_value: u64, // `PRICE_DECIMALS` decimals
+    pub payment_amount: u64, // Base units of the payment mint, or lamports
+    pub oracle_price: i64, // 0 for stablecoins
+    pub oracle_expo: i32,
+}
+
+/// Returned by `get_claimable_vested`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VestingQuote {
+    pub total: u64,
+    pub vested: u64,
+    pub claimed: u64,
+    pub claimable: u64,
+    pub timestamp: i64,
+}
+
+#[account]
+pub struct LiquidityLock {
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub unlock_at: i64,
+}
+
+impl LiquidityLock {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+}
+
+#[event]
+pub struct TokenPurchaseEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub price: u64,
+    pub allocation_type: u8,
+    pub payment_asset: Pubkey, // Payment mint, or the native mint for SOL
+    pub payment_amount: u64,
+    pub oracle_price: i64, // 0 when not oracle-priced
+    pub oracle_expo: i32,
+    pub max_payment: u64, // Buyer's slippage bound
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenClaimEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentMintUpdated {
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct SaleRoundCreated {
+    pub allocation_type: u8,
+    pub round_index: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub allowlisted: bool,
+}
+
+#[event]
+pub struct SaleRoundPauseUpdated {
+    pub allocation_type: u8,
+    pub paused: bool,
+}
+
+#[event]
+pub struct SaleRoundFinalized {
+    pub allocation_type: u8,
+    pub round_index: u32,
+    pub sold: u64,
+    pub succeeded: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundProceedsWithdrawn {
+    pub allocation_type: u8,
+    pub payment_asset: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PresaleRefunded {
     pub user: Pubkey,
     pub allocation_type: u8,
-    pub amount_purchased: u64,
-    pub amount_claimed: u64,
-    pub total_paid: u64,
-    pub purchase_timestamp: i64,
-    pub last_purchase: i64,
-    pub last_claim: i64,
+    pub payment_asset: Pubkey,
+    pub amount: u64,
 }
 
-impl UserPurchase {
-    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 8 + 8;
+#[event]
+pub struct TgeTimestampSet {
+    pub tge_timestamp: i64,
+    pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenPurchaseEvent {
-    pub user: Pubkey,
+pub struct VestingGrantCreated {
+    pub beneficiary: Pubkey,
+    pub allocation_type: u8,
     pub amount: u64,
-    pub price: u64,
+    pub start_timestamp: i64,
+    pub revocable: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrantRevoked {
+    pub beneficiary: Pubkey,
     pub allocation_type: u8,
+    pub vested_amount: u64,
+    pub clawed_back: u64,
     pub timestamp: i64,
 }
 
 #[event]
-pub struct TokenClaimEvent {
+pub struct MilestoneDefined {
+    pub milestone_id: u64,
+    pub name: String,
+    pub amount: u64,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct MilestoneAttested {
+    pub milestone_id: u64,
+    pub amount: u64,
+    pub attested_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LiquidityBootstrapped {
+    pub pool: Pubkey,
+    pub arkly_amount: u64,
+    pub quote_mint: Pubkey,
+    pub quote_amount: u64,
+    pub lp_amount: u64,
+    pub unlock_at: i64,
+}
+
+#[event]
+pub struct LockedLpWithdrawn {
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct RevenueDeposited {
+    pub quote_mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct Buyback {
+    pub caller: Pubkey,
+    pub quote_mint: Pubkey,
+    pub amount_in: u64,
+    pub arkly_burned: u64,
+    pub total_burned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionTokenized {
+    pub lot: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct PositionTransferred {
+    pub position_mint: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub approver: Option<Pubkey>,
+}
+
+#[event]
+pub struct AirdropCampaignCreated {
+    pub campaign_id: u64,
+    pub total_amount: u64,
+    pub claim_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AirdropClaimed {
+    pub campaign_id: u64,
     pub user: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AirdropSwept {
+    pub campaign_id: u64,
+    pub unclaimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Paused {
+    pub scope: PauseScope,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct Unpaused {
+    pub scope: PauseScope,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Invalid allocation type")]
@@ -342,45 +3521,861 @@ pub enum ErrorCode {
     NoTokensToCllaim,
     #[msg("Cliff period not reached")]
     CliffNotReached,
-    #[msg("Unauthorized")]
-    Unauthorized,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("TGE has not been reached")]
+    TgeNotReached,
+    #[msg("TGE has already been reached")]
+    TgeAlreadyReached,
+    #[msg("TGE timestamp must not be in the past")]
+    InvalidTgeTimestamp,
+    #[msg("Grant is not revocable")]
+    GrantNotRevocable,
+    #[msg("Grant has already been revoked")]
+    GrantAlreadyRevoked,
+    #[msg("Lot passed more than once")]
+    DuplicateLot,
+    #[msg("Sale round is not open")]
+    RoundNotOpen,
+    #[msg("Sale round is paused")]
+    RoundPaused,
+    #[msg("Sale round has been finalized")]
+    RoundFinalized,
+    #[msg("Purchase below the round minimum")]
+    BelowMinPurchase,
+    #[msg("Purchase exceeds the per-wallet limit")]
+    WalletLimitExceeded,
+    #[msg("Sale round hard cap reached")]
+    HardCapReached,
+    #[msg("Buyer is not on the round allowlist")]
+    NotAllowlisted,
+    #[msg("Invalid sale round configuration")]
+    InvalidRoundConfig,
+    #[msg("Payment mint is not accepted")]
+    UnsupportedPaymentMint,
+    #[msg("Payment mint is already accepted")]
+    PaymentMintAlreadyAccepted,
+    #[msg("Too many payment mints")]
+    TooManyPaymentMints,
+    #[msg("Treasury account does not match the payment mint")]
+    InvalidTreasury,
+    #[msg("Payment account must be the buyer's account for the payment mint")]
+    InvalidPaymentAccount,
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+    #[msg("Price feed does not match the configured oracle")]
+    InvalidPriceFeed,
+    #[msg("Oracle price is not currently trading")]
+    OraclePriceUnavailable,
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    #[msg("Oracle confidence interval too wide")]
+    OracleConfidenceTooWide,
+    #[msg("Payment exceeds the buyer's slippage bound")]
+    SlippageExceeded,
+    #[msg("Airdrop deadline must be in the future")]
+    InvalidAirdropDeadline,
+    #[msg("Airdrop claim deadline has passed")]
+    AirdropExpired,
+    #[msg("Airdrop is still claimable")]
+    AirdropStillActive,
+    #[msg("Airdrop has already been swept")]
+    AirdropAlreadySwept,
+    #[msg("Invalid airdrop proof")]
+    InvalidAirdropProof,
+    #[msg("Mint does not match the ARKLY mint")]
+    InvalidMint,
+    #[msg("Milestone name must be 1-32 bytes")]
+    InvalidMilestoneName,
+    #[msg("Milestone has already been met")]
+    MilestoneAlreadyMet,
+    #[msg("Recipient account does not match the milestone")]
+    InvalidRecipient,
+    #[msg("Would exceed the total supply")]
+    SupplyCapExceeded,
+    #[msg("Mint authority must be the token info PDA with no freeze authority")]
+    InvalidMintAuthority,
+    #[msg("Mint decimals do not match")]
+    InvalidMintDecimals,
+    #[msg("Sale round is still open and below its soft cap")]
+    RoundStillOpen,
+    #[msg("Sale round did not succeed")]
+    RoundNotSucceeded,
+    #[msg("Sale round did not fail")]
+    RoundNotFailed,
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    #[msg("AMM program is not the registered one")]
+    InvalidAmmProgram,
+    #[msg("Round proceeds are insufficient")]
+    InsufficientProceeds,
+    #[msg("Liquidity is still locked")]
+    LiquidityLocked,
+    #[msg("Invalid buyback configuration")]
+    InvalidBuybackConfig,
+    #[msg("Buyback exceeds the per-execution limit")]
+    BuybackLimitExceeded,
+    #[msg("Buyback interval has not elapsed")]
+    BuybackCooldown,
+    #[msg("Revenue vault balance is insufficient")]
+    InsufficientRevenue,
+    #[msg("Lot is held as a position token")]
+    PositionTokenized,
+    #[msg("Account does not hold this position")]
+    InvalidPosition,
+    #[msg("Position transfer requires authority approval")]
+    TransferNotApproved,
+    #[msg("Purchases are paused")]
+    PurchasesPaused,
+    #[msg("Claims are paused")]
+    ClaimsPaused,
+    #[msg("Airdrop claims are paused")]
+    AirdropsPaused,
+    #[msg("Allocations exceed the total supply")]
+    AllocationsExceedSupply,
+    #[msg("Mint already has supply")]
+    MintAlreadyHasSupply,
+    #[msg("Liquidity allocation has not vested this far")]
+    LiquidityNotVested,
+    #[msg("A sale round for this allocation is open or has succeeded")]
+    SaleRoundExists,
+    #[msg("Purchase record belongs to another sale round")]
+    LotInAnotherRound,
+}
+
+const DAY: i64 = 24 * 60 * 60;
+const MONTH: i64 = 30 * DAY;
+
+/// Decimals of `AllocationInfo.price`
+const PRICE_DECIMALS: u32 = 9;
+const MAX_PAYMENT_MINTS: usize = 4;
+const SOL_DECIMALS: u8 = 9;
+const LIQUIDITY_ALLOCATION: u8 = 2;
+const TREASURY_ALLOCATION: u8 = 5;
+const AIRDROP_ALLOCATION: u8 = 7;
+const MAX_MILESTONE_NAME_LEN: usize = 32;
+
+// Pyth v2 price account layout
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_PRICE_ACCOUNT_LEN: usize = 240;
+
+/// Move lamports out of an account owned by this program
+fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_balance = checked_sub(from.lamports(), amount)?;
+    let to_balance = checked_add(to.lamports(), amount)?;
+    
+    **from.try_borrow_mut_lamports()? = from_balance;
+    **to.try_borrow_mut_lamports()? = to_balance;
+    
+    Ok(())
+}
+
+/// Reserve tokens from an allocation, keeping all reservations within the total supply
+fn reserve_allocation(token_info: &mut TokenInfo, allocation_type: u8, amount: u64) -> Result<()> {
+    require!(
+        checked_add(token_info.allocations.total_released()?, amount)? <= token_info.total_supply,
+        ErrorCode::SupplyCapExceeded
+    );
+    
+    let allocation = token_info.allocations.get_mut(allocation_type)?;
+    require!(
+        amount <= checked_sub(allocation.amount, allocation.released)?,
+        ErrorCode::InsufficientAllocation
+    );
+    allocation.released = checked_add(allocation.released, amount)?;
+    
+    Ok(())
+}
+
+/// Mint ARKLY signed by the `token_info` PDA, enforcing the supply cap
+fn mint_arkly<'info>(
+    token_info: &mut Account<'info, TokenInfo>,
+    mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    bump: u8,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        checked_add(token_info.total_minted, amount)? <= token_info.total_supply,
+        ErrorCode::SupplyCapExceeded
+    );
+    
+    let seeds = &[
+        seeds::TOKEN_INFO,
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = token::MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: token_info.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::mint_to(cpi_ctx, amount)?;
+    
+    // Tokens circulate once minted; buyback burns take them back out
+    token_info.total_minted = checked_add(token_info.total_minted, amount)?;
+    token_info.circulating_supply = checked_add(token_info.circulating_supply, amount)?;
+    
+    Ok(())
+}
+
+/// Build a call into the registered AMM: an Anchor-style sighash followed by borsh args
+fn amm_instruction(
+    program_id: Pubkey,
+    name: &str,
+    accounts: Vec<AccountMeta>,
+    args: Vec<u8>,
+) -> Instruction {
+    let preimage = format!("global:{}", name);
+    let mut data = hash::hash(preimage.as_bytes()).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&args);
+    
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Token authority, or governance once registered
+fn is_authority_or_governance(token_info: &TokenInfo, key: Pubkey) -> bool {
+    key == token_info.authority
+        || (token_info.governance != Pubkey::default() && key == token_info.governance)
+}
+
+/// Check round, wallet and allocation limits for a presale purchase and return the token price
+#[allow(clippy::too_many_arguments)]
+fn validate_purchase(
+    token_info: &TokenInfo,
+    sale_round: &SaleRound,
+    user_purchase: &UserPurchase,
+    user: Pubkey,
+    amount: u64,
+    allocation_type: u8,
+    proof: &[[u8; 32]],
+    current_time: i64,
+) -> Result<u64> {
+    require!(!token_info.paused.purchases, ErrorCode::PurchasesPaused);
+    
+    // Validate the round is open
+    require!(!sale_round.paused, ErrorCode::RoundPaused);
+    require!(sale_round.status == RoundStatus::Active, ErrorCode::RoundFinalized);
+    require!(
+        current_time >= sale_round.start_time && current_time < sale_round.end_time,
+        ErrorCode::RoundNotOpen
+    );
+    
+    // A tokenized lot belongs to its position holder and cannot be topped up
+    require!(
+        user_purchase.position_mint == Pubkey::default(),
+        ErrorCode::PositionTokenized
+    );
+    
+    // A lot belongs to one round; a fully refunded lot can be reused in a later one
+    let lot_is_empty = user_purchase.amount_purchased == 0
+        && user_purchase.payments.iter().all(|payment| payment.amount == 0);
+    require!(
+        lot_is_empty || user_purchase.round_index == sale_round.round_index,
+        ErrorCode::LotInAnotherRound
+    );
+    
+    // Validate per-wallet limits and the round hard cap
+    require!(amount >= sale_round.min_purchase, ErrorCode::BelowMinPurchase);
+    require!(
+        checked_add(user_purchase.amount_purchased, amount)? <= sale_round.max_per_wallet,
+        ErrorCode::WalletLimitExceeded
+    );
+    require!(
+        checked_add(sale_round.sold, amount)? <= sale_round.hard_cap,
+        ErrorCode::HardCapReached
+    );
+    
+    // Allowlisted rounds require a Merkle proof for the buyer
+    if sale_round.merkle_root != [0u8; 32] {
+        let leaf = keccak::hashv(&[user.as_ref()]).0;
+        require!(
+            verify_merkle_proof(proof, sale_round.merkle_root, leaf),
+            ErrorCode::NotAllowlisted
+        );
+    }
+    
+    // Validate allocation type and availability
+    require!(
+        matches!(allocation_type, 0 | 1),
+        ErrorCode::InvalidAllocationType
+    );
+    let allocation = token_info.allocations.get(allocation_type)?;
+    
+    require!(
+        checked_add(allocation.released, amount)? <= allocation.amount,
+        ErrorCode::InsufficientAllocation
+    );
+    
+    Ok(allocation.price)
+}
+
+/// Update the buyer's lot, the round and the allocation after payment
+#[allow(clippy::too_many_arguments)]
+fn record_purchase(
+    token_info: &mut TokenInfo,
+    sale_round: &mut SaleRound,
+    user_purchase: &mut UserPurchase,
+    user: Pubkey,
+    amount: u64,
+    allocation_type: u8,
+    price: u64,
+    payment_asset: Pubkey,
+    payment_amount: u64,
+    current_time: i64,
+) -> Result<()> {
+    // Update purchase record; `total_paid` is the USD value whatever the payment asset
+    user_purchase.user = user;
+    user_purchase.allocation_type = allocation_type;
+    if user_purchase.round_index != sale_round.round_index {
+        // Reused after a full refund in an earlier round
+        user_purchase.payments.clear();
+        user_purchase.round_index = sale_round.round_index;
+    }
+    let usd_value = calculate_payment(amount, price, token_info.decimals, PRICE_DECIMALS as u8)?;
+    user_purchase.amount_purchased = checked_add(user_purchase.amount_purchased, amount)?;
+    user_purchase.total_paid = checked_add(user_purchase.total_paid, usd_value)?;
+    user_purchase.vesting_start = 0; // Presale buyers all vest from TGE
+    user_purchase.last_purchase = current_time;
+    
+    match user_purchase.payments.iter_mut().find(|payment| payment.asset == payment_asset) {
+        Some(payment) => payment.amount = checked_add(payment.amount, payment_amount)?,
+        None => user_purchase.payments.push(AssetPayment {
+            asset: payment_asset,
+            amount: payment_amount,
+        }),
+    }
+    
+    // Update allocation
+    sale_round.sold = checked_add(sale_round.sold, amount)?;
+    reserve_allocation(token_info, allocation_type, amount)?;
+    
+    Ok(())
+}
+
+/// Zero and return a lot's escrowed payment in `asset`
+fn take_refund(user_purchase: &mut UserPurchase, asset: Pubkey) -> Result<u64> {
+    let payment = user_purchase
+        .payments
+        .iter_mut()
+        .find(|payment| payment.asset == asset && payment.amount > 0)
+        .ok_or(ErrorCode::NothingToRefund)?;
+    
+    let amount = payment.amount;
+    payment.amount = 0;
+    
+    Ok(amount)
+}
+
+/// Return a refunded lot's tokens to the allocation, once per lot
+fn return_refunded_lot(token_info: &mut TokenInfo, user_purchase: &mut UserPurchase) -> Result<()> {
+    if user_purchase.amount_purchased == 0 {
+        return Ok(());
+    }
+    
+    let allocation = token_info.allocations.get_mut(user_purchase.allocation_type)?;
+    allocation.released = checked_sub(allocation.released, user_purchase.amount_purchased)?;
+    
+    user_purchase.amount_purchased = 0;
+    user_purchase.total_paid = 0;
+    
+    Ok(())
 }
 
-/// Calculate vested amount based on cliff and vesting schedule
+/// Read and sanity-check a price from the configured oracle feed
+fn read_oracle_price(
+    price_feed: &AccountInfo,
+    config: &OracleConfig,
+    current_time: i64,
+) -> Result<OraclePrice> {
+    require!(
+        price_feed.key() == config.feed && *price_feed.owner == config.program,
+        ErrorCode::InvalidPriceFeed
+    );
+    
+    let data = price_feed.try_borrow_data()?;
+    let oracle_price = match config.kind {
+        OracleKind::Pyth => parse_pyth_price(&data)?,
+    };
+    
+    require!(oracle_price.price > 0, ErrorCode::OraclePriceUnavailable);
+    require!(
+        current_time.saturating_sub(oracle_price.publish_time) <= config.max_staleness,
+        ErrorCode::StaleOraclePrice
+    );
+    require!(
+        oracle_price.conf as u128 * 10_000
+            <= oracle_price.price as u128 * config.max_confidence_bps as u128,
+        ErrorCode::OracleConfidenceTooWide
+    );
+    
+    Ok(oracle_price)
+}
+
+/// Decode the aggregate price of a Pyth v2 price account
+fn parse_pyth_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(data.len() >= PYTH_PRICE_ACCOUNT_LEN, ErrorCode::InvalidPriceFeed);
+    
+    let read_u32 = |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+    let read_u64 = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+    
+    require!(
+        read_u32(0) == PYTH_MAGIC && read_u32(8) == PYTH_PRICE_ACCOUNT_TYPE,
+        ErrorCode::InvalidPriceFeed
+    );
+    require!(read_u32(224) == PYTH_STATUS_TRADING, ErrorCode::OraclePriceUnavailable);
+    
+    Ok(OraclePrice {
+        price: read_u64(208) as i64,
+        conf: read_u64(216),
+        expo: read_u32(20) as i32,
+        publish_time: read_u64(96) as i64,
+    })
+}
+
+/// Convert a token amount at `price` into base units of an oracle-priced asset, rounding up
+fn calculate_oracle_payment(
+    amount: u64,
+    price: u64,
+    token_decimals: u8,
+    oracle_price: &OraclePrice,
+    asset_decimals: u8,
+) -> Result<u64> {
+    // payment = amount * price * 10^asset_decimals
+    //         / (10^token_decimals * 10^PRICE_DECIMALS * oracle_price * 10^expo)
+    let scale = asset_decimals as i32 - token_decimals as i32 - PRICE_DECIMALS as i32 - oracle_price.expo;
+    
+    let mut numerator = (amount as u128)
+        .checked_mul(price as u128)
+        .ok_or(CommonError::MathOverflow)?;
+    let mut denominator = oracle_price.price as u128;
+    if scale >= 0 {
+        numerator = numerator
+            .checked_mul(10u128.checked_pow(scale as u32).ok_or(CommonError::MathOverflow)?)
+            .ok_or(CommonError::MathOverflow)?;
+    } else {
+        denominator = denominator
+            .checked_mul(10u128.checked_pow((-scale) as u32).ok_or(CommonError::MathOverflow)?)
+            .ok_or(CommonError::MathOverflow)?;
+    }
+    
+    let payment = math::div(numerator, denominator, Rounding::Up)?;
+    require!(payment > 0, ErrorCode::InvalidAmount);
+    
+    u64::try_from(payment).map_err(|_| CommonError::MathOverflow.into())
+}
+
+/// Convert a token amount at `price` into payment mint base units, rounding up
+fn calculate_payment(
+    amount: u64,
+    price: u64,
+    token_decimals: u8,
+    payment_decimals: u8,
+) -> Result<u64> {
+    let numerator = (amount as u128)
+        .checked_mul(price as u128)
+        .and_then(|value| value.checked_mul(10u128.checked_pow(payment_decimals as u32)?))
+        .ok_or(CommonError::MathOverflow)?;
+    let denominator = 10u128
+        .checked_pow(token_decimals as u32 + PRICE_DECIMALS)
+        .ok_or(CommonError::MathOverflow)?;
+    let payment = math::div(numerator, denominator, Rounding::Up)?;
+    
+    require!(payment > 0, ErrorCode::InvalidAmount);
+    
+    u64::try_from(payment).map_err(|_| CommonError::MathOverflow.into())
+}
+
+/// Tokens (in base units) bought by `payment` at `price`, rounded up so a price floor
+/// never accepts less than `price` is worth
+fn calculate_tokens_for_payment(
+    payment: u64,
+    price: u64,
+    token_decimals: u8,
+    payment_decimals: u8,
+) -> Result<u64> {
+    let numerator = 10u128
+        .checked_pow(token_decimals as u32 + PRICE_DECIMALS)
+        .and_then(|scale| (payment as u128).checked_mul(scale))
+        .ok_or(CommonError::MathOverflow)?;
+    let denominator = 10u128
+        .checked_pow(payment_decimals as u32)
+        .and_then(|scale| (price as u128).checked_mul(scale))
+        .ok_or(CommonError::MathOverflow)?;
+    
+    u64::try_from(math::div(numerator, denominator, Rounding::Up)?).map_err(|_| CommonError::MathOverflow.into())
+}
+
+/// Verify a keccak Merkle proof; pairs are hashed in sorted order
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed = leaf;
+    for node in proof.iter() {
+        computed = if computed <= *node {
+            keccak::hashv(&[&computed, node]).0
+        } else {
+            keccak::hashv(&[node, &computed]).0
+        };
+    }
+    computed == root
+}
+
+/// Freeze a grant at what has vested and return the unvested remainder to its allocation.
+/// Returns the vested and clawed-back amounts.
+fn revoke_vesting_grant(
+    token_info: &mut TokenInfo,
+    grant: &mut UserPurchase,
+    current_time: i64,
+) -> Result<(u64, u64)> {
+    let vested_amount = calculate_vested_total(grant, token_info, current_time)?;
+    let clawed_back = checked_sub(grant.amount_purchased, vested_amount)?;
+    
+    let allocation = token_info.allocations.get_mut(grant.allocation_type)?;
+    allocation.released = checked_sub(allocation.released, clawed_back)?;
+    
+    // The vested part stays claimable immediately, by the holder if tokenized
+    grant.amount_purchased = vested_amount;
+    grant.revoked = true;
+    
+    Ok((vested_amount, clawed_back))
+}
+
+/// Calculate the claimable amount: everything vested so far minus what was claimed
 fn calculate_vested_amount(
     user_purchase: &UserPurchase,
     token_info: &TokenInfo,
     current_timestamp: i64,
 ) -> Result<u64> {
-    let allocation = match user_purchase.allocation_type {
-        0 => &token_info.allocations.seed_round,
-        1 => &token_info.allocations.public_presale,
-        2 => &token_info.allocations.liquidity_pool,
-        3 => &token_info.allocations.team_advisors,
-        4 => &token_info.allocations.ecosystem_rewards,
-        5 => &token_info.allocations.treasury_dev,
-        6 => &token_info.allocations.strategic_partners,
-        7 => &token_info.allocations.community_airdrops,
-        _ => return Err(ErrorCode::InvalidAllocationType.into()),
-    };
+    let vested_amount = calculate_vested_total(user_purchase, token_info, current_timestamp)?;
+    
+    Ok(vested_amount.saturating_sub(user_purchase.amount_claimed))
+}
+
+/// Calculate total vested amount: TGE unlock at the vesting start, then linear per-second
+/// unlock over `vesting_duration` once the cliff has passed
+fn calculate_vested_total(
+    user_purchase: &UserPurchase,
+    token_info: &TokenInfo,
+    current_timestamp: i64,
+) -> Result<u64> {
+    // A revoked grant was frozen at its vested amount
+    if user_purchase.revoked {
+        return Ok(user_purchase.amount_purchased);
+    }
+    
+    // Presale lots only vest once their round has succeeded
+    if !user_purchase.round_succeeded(token_info) {
+        return Ok(0);
+    }
     
-    let months_since_purchase = (current_timestamp - user_purchase.purchase_timestamp) / (30 * 24 * 60 * 60);
+    let allocation = token_info.allocations.get(user_purchase.allocation_type)?;
+    
+    if token_info.tge_timestamp == 0 || current_timestamp < token_info.tge_timestamp {
+        return Ok(0);
+    }
     
-    // Check cliff period
-    if months_since_purchase < allocation.cliff_months as i64 {
+    // Nothing vests before TGE, even for grants dated earlier
+    let vesting_start = user_purchase.vesting_start.max(token_info.tge_timestamp);
+    if current_timestamp < vesting_start {
         return Ok(0);
     }
     
-    // Calculate vested amount
-    let vested_amount = if allocation.vesting_months == 0 {
-        // Immediate vesting (like public presale)
-        user_purchase.amount_purchased
+    vested_share(user_purchase.amount_purchased, allocation, current_timestamp - vesting_start)
+}
+
+/// Portion of `total` unlocked `elapsed` seconds after the vesting start on an
+/// allocation's TGE unlock, cliff and linear schedule
+fn vested_share(total: u64, allocation: &AllocationInfo, elapsed: i64) -> Result<u64> {
+    let tge_unlock = mul_div_floor(total, allocation.tge_unlock_bps as u64, 10_000)?;
+    
+    let vested_amount = if elapsed < allocation.cliff_duration {
+        tge_unlock
+    } else if allocation.vesting_duration == 0 {
+        // Remainder unlocks in full at the end of the cliff
+        total
     } else {
-        let vesting_months_passed = (months_since_purchase - allocation.cliff_months as i64)
-            .min(allocation.vesting_months as i64);
+        let vesting_elapsed = (elapsed - allocation.cliff_duration)
+            .min(allocation.vesting_duration) as u64;
         
-        user_purchase.amount_purchased * vesting_months_passed as u64 / allocation.vesting_months as u64
+        // Rounds down: the last base unit only unlocks at the end of vesting
+        let linear = mul_div_floor(
+            checked_sub(total, tge_unlock)?,
+            vesting_elapsed,
+            allocation.vesting_duration as u64,
+        )?;
+        checked_add(tge_unlock, linear)?
     };
     
-    Ok(vested_amount.saturating_sub(user_purchase.amount_claimed))
+    Ok(vested_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TGE: i64 = 1_700_000_000;
+    const TOTAL_SUPPLY: u64 = 100_000_000_000_000_000; // 100M with 9 decimals
+    const TEAM_ALLOCATION: u8 = 3;
+
+    fn token_info() -> TokenInfo {
+        TokenInfo {
+            total_supply: TOTAL_SUPPLY,
+            circulating_supply: 0,
+            total_minted: 0,
+            total_burned: 0,
+            decimals: 9,
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            tge_timestamp: TGE,
+            governance: Pubkey::default(),
+            accepted_payments: Vec::new(),
+            sol_payment: SolPaymentConfig::default(),
+            airdrop_campaign_count: 0,
+            milestone_count: 0,
+            presale_round_succeeded: [true; 2],
+            sale_round_count: [1; 2],
+            sale_round_open: [false; 2],
+            amm_program: Pubkey::default(),
+            position_transfer_approval: false,
+            pending_authority: Pubkey::default(),
+            roles: Roles::default(),
+            paused: PauseFlags::default(),
+            allocations: TokenomicsAllocations::new(TOTAL_SUPPLY).unwrap(),
+        }
+    }
+
+    fn grant(token_info: &mut TokenInfo, allocation_type: u8, amount: u64) -> UserPurchase {
+        reserve_allocation(token_info, allocation_type, amount).unwrap();
+
+        UserPurchase {
+            user: Pubkey::new_unique(),
+            allocation_type,
+            amount_purchased: amount,
+            amount_claimed: 0,
+            total_paid: 0,
+            vesting_start: 0,
+            last_purchase: 0,
+            last_claim: 0,
+            revocable: true,
+            revoked: false,
+            payments: Vec::new(),
+            position_mint: Pubkey::default(),
+            round_index: 0,
+        }
+    }
+
+    #[test]
+    fn allocations_add_up_to_the_total_supply() {
+        let allocations = TokenomicsAllocations::new(TOTAL_SUPPLY).unwrap();
+
+        assert_eq!(allocations.total_amount().unwrap(), TOTAL_SUPPLY);
+    }
+
+    #[test]
+    fn nothing_vests_before_tge_or_the_cliff() {
+        let mut token_info = token_info();
+        let grant = grant(&mut token_info, TEAM_ALLOCATION, 2_400);
+
+        assert_eq!(calculate_vested_total(&grant, &token_info, TGE - 1).unwrap(), 0);
+        assert_eq!(calculate_vested_total(&grant, &token_info, TGE + 12 * MONTH - 1).unwrap(), 0);
+
+        token_info.tge_timestamp = 0;
+        assert_eq!(calculate_vested_total(&grant, &token_info, TGE + 48 * MONTH).unwrap(), 0);
+    }
+
+    #[test]
+    fn tge_share_unlocks_before_the_cliff_and_the_rest_at_it() {
+        let mut token_info = token_info();
+        let lot = grant(&mut token_info, LIQUIDITY_ALLOCATION, 1_001);
+
+        // 50% at TGE (rounded down), everything at the end of the 30-day cliff
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE).unwrap(), 500);
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE + 30 * DAY - 1).unwrap(), 500);
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE + 30 * DAY).unwrap(), 1_001);
+    }
+
+    #[test]
+    fn linear_vesting_starts_at_the_cliff_and_rounds_down() {
+        let mut token_info = token_info();
+        let grant = grant(&mut token_info, TEAM_ALLOCATION, 1_000_001);
+        let cliff_end = TGE + 12 * MONTH;
+
+        assert_eq!(calculate_vested_total(&grant, &token_info, cliff_end).unwrap(), 0);
+        assert_eq!(calculate_vested_total(&grant, &token_info, cliff_end + 12 * MONTH).unwrap(), 500_000);
+        assert_eq!(calculate_vested_total(&grant, &token_info, cliff_end + 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn everything_vests_after_the_full_schedule() {
+        let mut token_info = token_info();
+        let mut grant = grant(&mut token_info, TEAM_ALLOCATION, 1_000_001);
+        let vesting_end = TGE + 36 * MONTH;
+
+        assert_eq!(calculate_vested_total(&grant, &token_info, vesting_end - 1).unwrap(), 1_000_000);
+        assert_eq!(calculate_vested_total(&grant, &token_info, vesting_end).unwrap(), 1_000_001);
+        assert_eq!(calculate_vested_total(&grant, &token_info, vesting_end + 365 * DAY).unwrap(), 1_000_001);
+
+        grant.amount_claimed = 400_000;
+        assert_eq!(calculate_vested_amount(&grant, &token_info, vesting_end).unwrap(), 600_001);
+    }
+
+    #[test]
+    fn a_grant_starting_after_tge_vests_from_its_own_start() {
+        let mut token_info = token_info();
+        let mut grant = grant(&mut token_info, TEAM_ALLOCATION, 2_400);
+        grant.vesting_start = TGE + 6 * MONTH;
+        let cliff_end = grant.vesting_start + 12 * MONTH;
+
+        assert_eq!(calculate_vested_total(&grant, &token_info, cliff_end - 1).unwrap(), 0);
+        assert_eq!(calculate_vested_total(&grant, &token_info, cliff_end + 6 * MONTH).unwrap(), 600);
+    }
+
+    #[test]
+    fn presale_lots_vest_only_with_the_round_that_succeeded() {
+        let mut token_info = token_info();
+        let mut lot = grant(&mut token_info, 1, 1_000);
+        lot.revocable = false;
+
+        // Bought in round 0, which failed; round 1 then succeeded
+        token_info.sale_round_count = [1, 2];
+        assert!(!lot.round_succeeded(&token_info));
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE).unwrap(), 0);
+
+        lot.round_index = 1;
+        assert!(lot.round_succeeded(&token_info));
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE).unwrap(), 1_000);
+
+        token_info.presale_round_succeeded[1] = false;
+        assert_eq!(calculate_vested_total(&lot, &token_info, TGE).unwrap(), 0);
+    }
+
+    #[test]
+    fn next_round_index_ignores_non_presale_allocations() {
+        let mut token_info = token_info();
+        token_info.sale_round_count = [3, 0];
+
+        assert_eq!(token_info.next_round_index(0), 3);
+        assert_eq!(token_info.next_round_index(1), 0);
+        assert_eq!(token_info.next_round_index(TEAM_ALLOCATION), 0);
+    }
+
+    #[test]
+    fn revocation_freezes_the_vested_amount_and_returns_the_rest() {
+        let mut token_info = token_info();
+        let mut grant = grant(&mut token_info, TEAM_ALLOCATION, 2_400);
+        let revoked_at = TGE + 18 * MONTH; // A quarter through linear vesting
+
+        let (vested, clawed_back) = revoke_vesting_grant(&mut token_info, &mut grant, revoked_at).unwrap();
+
+        assert_eq!((vested, clawed_back), (600, 1_800));
+        assert!(grant.revoked);
+        assert_eq!(grant.amount_purchased, 600);
+        assert_eq!(token_info.allocations.get(TEAM_ALLOCATION).unwrap().released, 600);
+
+        // Vesting no longer advances, and the vested part is claimable at once
+        assert_eq!(calculate_vested_total(&grant, &token_info, TGE + 48 * MONTH).unwrap(), 600);
+        assert_eq!(calculate_vested_amount(&grant, &token_info, revoked_at).unwrap(), 600);
+    }
+
+    #[test]
+    fn revoking_a_fully_vested_grant_claws_back_nothing() {
+        let mut token_info = token_info();
+        let mut grant = grant(&mut token_info, TEAM_ALLOCATION, 2_400);
+
+        let (vested, clawed_back) =
+            revoke_vesting_grant(&mut token_info, &mut grant, TGE + 36 * MONTH).unwrap();
+
+        assert_eq!((vested, clawed_back), (2_400, 0));
+        assert_eq!(token_info.allocations.get(TEAM_ALLOCATION).unwrap().released, 2_400);
+    }
+
+    #[test]
+    fn revoking_a_tokenized_grant_leaves_the_holder_the_vested_remainder() {
+        let mut token_info = token_info();
+        let mut grant = grant(&mut token_info, TEAM_ALLOCATION, 2_400);
+        let position_mint = Pubkey::new_unique();
+        grant.position_mint = position_mint;
+        grant.amount_claimed = 200; // Claimed by the position holder before revocation
+
+        let revoked_at = TGE + 24 * MONTH; // Halfway through linear vesting
+        let (vested, clawed_back) = revoke_vesting_grant(&mut token_info, &mut grant, revoked_at).unwrap();
+
+        assert_eq!((vested, clawed_back), (1_200, 1_200));
+        assert_eq!(grant.position_mint, position_mint);
+        assert_eq!(calculate_vested_amount(&grant, &token_info, revoked_at).unwrap(), 1_000);
+        assert_eq!(calculate_vested_amount(&grant, &token_info, TGE + 48 * MONTH).unwrap(), 1_000);
+        assert_eq!(token_info.allocations.get(TEAM_ALLOCATION).unwrap().released, 1_200);
+    }
+
+    fn oracle_price(price: i64, expo: i32) -> OraclePrice {
+        OraclePrice { price, conf: 0, expo, publish_time: 0 }
+    }
+
+    #[test]
+    fn payments_round_up_to_the_next_base_unit() {
+        // 0.05 USDC per ARKLY, USDC has 6 decimals
+        let price = 50_000_000;
+
+        assert_eq!(calculate_payment(1_000_000_000, price, 9, 6).unwrap(), 50_000);
+        assert_eq!(calculate_payment(1_000_000_001, price, 9, 6).unwrap(), 50_001);
+        assert_eq!(calculate_payment(1, price, 9, 6).unwrap(), 1);
+        assert_eq!(calculate_tokens_for_payment(50_000, price, 9, 6).unwrap(), 1_000_000_000);
+        assert_eq!(calculate_tokens_for_payment(1, price, 9, 6).unwrap(), 20_000);
+    }
+
+    #[test]
+    fn payments_reject_zero_and_overflow() {
+        let overflow = Error::from(CommonError::MathOverflow);
+
+        assert_eq!(calculate_payment(1_000, 0, 9, 6).unwrap_err(), ErrorCode::InvalidAmount.into());
+        assert_eq!(calculate_payment(u64::MAX, u64::MAX, 9, 18).unwrap_err(), overflow);
+        assert_eq!(calculate_payment(u64::MAX, 10_000_000_000, 0, 0).unwrap_err(), overflow);
+        assert_eq!(calculate_payment(1, 1, 9, 39).unwrap_err(), overflow);
+        assert_eq!(calculate_payment(1, 1, 30, 6).unwrap_err(), overflow);
+        assert_eq!(calculate_tokens_for_payment(1, 1, 30, 6).unwrap_err(), overflow);
+        assert_eq!(
+            calculate_tokens_for_payment(1, 0, 9, 6).unwrap_err(),
+            CommonError::DivisionByZero.into()
+        );
+    }
+
+    #[test]
+    fn oracle_payments_round_up() {
+        // 0.05 USD per ARKLY paid in SOL at 150 USD (Pyth exponent -8)
+        let sol = oracle_price(15_000_000_000, -8);
+
+        assert_eq!(calculate_oracle_payment(1_000_000_000, 50_000_000, 9, &sol, 9).unwrap(), 333_334);
+        assert_eq!(calculate_oracle_payment(3_000_000_000, 50_000_000, 9, &sol, 9).unwrap(), 1_000_000);
+        assert_eq!(calculate_oracle_payment(1, 50_000_000, 9, &sol, 9).unwrap(), 1);
+    }
+
+    #[test]
+    fn oracle_payments_reject_zero_and_overflow() {
+        let overflow = Error::from(CommonError::MathOverflow);
+
+        assert_eq!(
+            calculate_oracle_payment(1_000, 50_000_000, 9, &oracle_price(0, -8), 9).unwrap_err(),
+            CommonError::DivisionByZero.into()
+        );
+        assert_eq!(
+            calculate_oracle_payment(u64::MAX, u64::MAX, 9, &oracle_price(1, 0), 9).unwrap_err(),
+            overflow
+        );
+        assert_eq!(
+            calculate_oracle_payment(1, 1, 9, &oracle_price(1, -100), 9).unwrap_err(),
+            overflow
+        );
+        assert_eq!(
+            calculate_oracle_payment(1, 1, 9, &oracle_price(1, 100), 9).unwrap_err(),
+            overflow
+        );
+    }
 }
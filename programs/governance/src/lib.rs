@@ -1,5 +1,14 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::Discriminator;
 use anchor_spl::token::{Token, TokenAccount};
+use arkly_common::events::{AuthorityProposed, AuthorityTransferred};
+use arkly_common::math::{self, checked_add, checked_sub, to_u64, Rounding};
+use arkly_common::{seeds, CommonError};
 
 declare_id!("G0v3rn4nc3V0t1ngD4oM4n4g3m3ntSm4rtC0ntr4ct1d");
 
@@ -13,25 +22,44 @@ pub mod governance {
         min_proposal_stake: u64,
         voting_period: i64,
         execution_delay: i64,
+        reward_rate: u64,
+        emergency_config: EmergencyConfig,
+        council: CouncilConfig,
     ) -> Result<()> {
+        validate_emergency_config(&emergency_config)?;
+        validate_council_config(&council, voting_period, execution_delay)?;
+        
         let governance = &mut ctx.accounts.governance;
         
         governance.authority = ctx.accounts.authority.key();
+        governance.pending_authority = Pubkey::default();
         governance.arkly_mint = ctx.accounts.arkly_mint.key();
         governance.min_proposal_stake = min_proposal_stake;
         governance.voting_period = voting_period;
         governance.execution_delay = execution_delay;
         governance.proposal_count = 0;
         governance.total_staked = 0;
+        governance.governance_vault = ctx.accounts.governance_vault.key();
+        governance.reward_vault = ctx.accounts.reward_vault.key();
+        governance.reward_rate = reward_rate;
+        governance.reward_reserve = 0;
+        governance.reward_per_token_stored = 0;
+        governance.last_reward_update = Clock::get()?.unix_timestamp;
+        governance.emergency = emergency_config;
+        governance.council = council;
+        governance.council_epoch = 0;
+        governance.council_action_count = 0;
         
         Ok(())
     }
 
-    /// Create a new governance proposal
+    /// Create a new governance proposal.
+    /// The full proposal document lives off-chain at `uri`; `content_hash` is its sha256.
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         title: String,
-        description: String,
+        uri: String,
+        content_hash: [u8; 32],
         proposal_type: ProposalType,
         execution_data: Vec<u8>,
     ) -> Result<()> {
@@ -44,25 +72,57 @@ pub mod governance {
             ErrorCode::InsufficientStake
         );
         
+        validate_proposal_metadata(&title, &uri, &content_hash, &execution_data)?;
+        
+        // Reject anything that would not decode (or is not allowed) at execution time
+        match proposal_type {
+            ProposalType::ParameterChange => {
+                GovernanceParameter::try_from_slice(&execution_data)
+                    .map_err(|_| ErrorCode::InvalidExecutionData)?;
+            }
+            ProposalType::Emergency => {
+                let instructions = decode_instruction_bundle(&execution_data)?;
+                require!(!instructions.is_empty(), ErrorCode::InvalidExecutionData);
+                require!(
+                    instructions.iter().all(is_emergency_action),
+                    ErrorCode::ActionNotAllowedForEmergency
+                );
+            }
+            _ => {
+                decode_instruction_bundle(&execution_data)?;
+            }
+        }
+        
+        let voting_period = if proposal_type == ProposalType::Emergency {
+            governance.emergency.voting_period
+        } else {
+            governance.voting_period
+        };
+        
         proposal.id = governance.proposal_count;
         proposal.proposer = ctx.accounts.proposer.key();
         proposal.title = title;
-        proposal.description = description;
+        proposal.uri = uri;
+        proposal.content_hash = content_hash;
         proposal.proposal_type = proposal_type;
         proposal.execution_data = execution_data;
         proposal.votes_for = 0;
         proposal.votes_against = 0;
         proposal.status = ProposalStatus::Active;
         proposal.created_at = Clock::get()?.unix_timestamp;
-        proposal.voting_ends_at = proposal.created_at + governance.voting_period;
+        proposal.voting_ends_at = proposal.created_at
+            .checked_add(voting_period)
+            .ok_or(CommonError::MathOverflow)?;
         proposal.execution_eta = 0;
         
-        governance.proposal_count += 1;
+        governance.proposal_count = checked_add(governance.proposal_count, 1)?;
         
         emit!(ProposalCreated {
             proposal_id: proposal.id,
             proposer: proposal.proposer,
             title: proposal.title.clone(),
+            uri: proposal.uri.clone(),
+            content_hash: proposal.content_hash,
             proposal_type: proposal.proposal_type.clone(),
             voting_ends_at: proposal.voting_ends_at,
         });
@@ -93,12 +153,17 @@ pub mod governance {
             ErrorCode::AlreadyVoted
         );
         
-        let voting_power = ctx.accounts.voter_token_account.amount;
+        // Votes are weighted by stake, which stays locked until voting ends so the
+        // same tokens cannot be moved to another wallet and counted again
+        let stake_account = &mut ctx.accounts.stake_account;
+        let voting_power = stake_account.staked_amount;
+        require!(voting_power > 0, ErrorCode::InsufficientStakedAmount);
+        stake_account.voting_lock_until = stake_account.voting_lock_until.max(proposal.voting_ends_at);
         
         if support {
-            proposal.votes_for += voting_power;
+            proposal.votes_for = checked_add(proposal.votes_for, voting_power)?;
         } else {
-            proposal.votes_against += voting_power;
+            proposal.votes_against = checked_add(proposal.votes_against, voting_power)?;
         }
         
         voter_record.has_voted = true;
@@ -132,10 +197,21 @@ pub mod governance {
             ErrorCode::ProposalNotActive
         );
         
-        // Check if proposal passed (simple majority)
-        if proposal.votes_for > proposal.votes_against {
+        // Emergency proposals need quorum and a supermajority but skip the timelock;
+        // everything else passes on a simple majority
+        let (passed, execution_delay) = if proposal.proposal_type == ProposalType::Emergency {
+            let passed = emergency_vote_passed(&governance.emergency, proposal.votes_for, proposal.votes_against);
+            (passed, 0)
+        } else {
+            (proposal.votes_for > proposal.votes_against, governance.execution_delay)
+        };
+        
+        if passed {
             proposal.status = ProposalStatus::Queued;
-            proposal.execution_eta = Clock::get()?.unix_timestamp + governance.execution_delay;
+            proposal.execution_eta = Clock::get()?
+                .unix_timestamp
+                .checked_add(execution_delay)
+                .ok_or(CommonError::MathOverflow)?;
             
             emit!(ProposalQueued {
                 proposal_id: proposal.id,
@@ -153,8 +229,11 @@ pub mod governance {
     }
 
     /// Execute a queued proposal
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
+    /// A proposal cancelling a council action takes that action as its only remaining account.
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
         
         require!(
             proposal.status == ProposalStatus::Queued,
@@ -169,23 +248,55 @@ pub mod governance {
         // Execute based on proposal type
         match proposal.proposal_type {
             ProposalType::ParameterChange => {
-                // Execute parameter change logic
-                msg!("Executing parameter change proposal");
-            }
-            ProposalType::TreasurySpend => {
-                // Execute treasury spending logic
-                msg!("Executing treasury spending proposal");
-            }
-            ProposalType::ProtocolUpgrade => {
-                // Execute protocol upgrade logic
-                msg!("Executing protocol upgrade proposal");
+                let parameter = GovernanceParameter::try_from_slice(&proposal.execution_data)
+                    .map_err(|_| ErrorCode::InvalidExecutionData)?;
+                
+                if let GovernanceParameter::CancelCouncilAction(action_id) = parameter {
+                    let action_info = ctx
+                        .remaining_accounts
+                        .first()
+                        .ok_or(ErrorCode::InvalidExecutionData)?;
+                    let mut council_action: Account<'_, CouncilAction> = Account::try_from(action_info)?;
+                    
+                    cancel_council_action(&mut council_action, action_id)?;
+                    council_action.exit(ctx.program_id)?;
+                    
+                    emit!(CouncilActionCancelled {
+                        action_id,
+                        proposal_id: proposal.id,
+                        timestamp: Clock::get()?.unix_timestamp,
+                    });
+                } else {
+                    apply_parameter_change(
+                        &mut ctx.accounts.governance,
+                        parameter,
+                        Clock::get()?.unix_timestamp,
+                    )?;
+                }
             }
-            ProposalType::PropertyListing => {
-                // Execute property listing logic
-                msg!("Executing property listing proposal");
+            _ => {
+                // Treasury spends, upgrades, listings and emergency actions all
+                // run as a bundle of instructions signed by the governance PDA
+                let instructions = decode_instruction_bundle(&proposal.execution_data)?;
+                
+                if proposal.proposal_type == ProposalType::Emergency {
+                    require!(
+                        instructions.iter().all(is_emergency_action),
+                        ErrorCode::ActionNotAllowedForEmergency
+                    );
+                }
+                
+                execute_instruction_bundle(
+                    &instructions,
+                    ctx.accounts.governance.to_account_info(),
+                    ctx.remaining_accounts,
+                    *ctx.bumps.get("governance").unwrap(),
+                    [ctx.accounts.governance.governance_vault, ctx.accounts.governance.reward_vault],
+                )?;
             }
         }
         
+        let proposal = &mut ctx.accounts.proposal;
         proposal.status = ProposalStatus::Executed;
         
         emit!(ProposalExecuted {
@@ -198,9 +309,6 @@ pub mod governance {
 
     /// Stake ARKLY tokens for governance participation
     pub fn stake_tokens(ctx: Context<StakeTokens>, amount: u64) -> Result<()> {
-        let governance = &mut ctx.accounts.governance;
-        let stake_account = &mut ctx.accounts.stake_account;
-        
         // Transfer tokens to governance vault
         let cpi_accounts = anchor_spl::token::Transfer {
             from: ctx.accounts.user_token_account.to_account_info(),
@@ -211,9 +319,14 @@ pub mod governance {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         anchor_spl::token::transfer(cpi_ctx, amount)?;
         
-        stake_account.staked_amount += amount;
-        stake_account.last_stake_time = Clock::get()?.unix_timestamp;
-        governance.total_staked += amount;
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.user = ctx.accounts.user.key();
+        apply_stake(
+            &mut ctx.accounts.governance,
+            stake_account,
+            amount,
+            Clock::get()?.unix_timestamp,
+        )?;
         
         emit!(TokensStaked {
             user: ctx.accounts.user.key(),
@@ -227,16 +340,15 @@ pub mod governance {
 
     /// Unstake ARKLY tokens
     pub fn unstake_tokens(ctx: Context<UnstakeTokens>, amount: u64) -> Result<()> {
-        let governance = &mut ctx.accounts.governance;
-        let stake_account = &mut ctx.accounts.stake_account;
-        
-        require!(
-            stake_account.staked_amount >= amount,
-            ErrorCode::InsufficientStakedAmount
-        );
+        apply_unstake(
+            &mut ctx.accounts.governance,
+            &mut ctx.accounts.stake_account,
+            amount,
+            Clock::get()?.unix_timestamp,
+        )?;
         
         // Transfer tokens back to user
-        let seeds = &[b"governance", &[ctx.bumps.governance]];
+        let seeds = &[seeds::GOVERNANCE, &[*ctx.bumps.get("governance").unwrap()]];
         let signer = &[&seeds[..]];
         
         let cpi_accounts = anchor_spl::token::Transfer {
@@ -248,18 +360,654 @@ pub mod governance {
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         anchor_spl::token::transfer(cpi_ctx, amount)?;
         
-        stake_account.staked_amount -= amount;
-        governance.total_staked -= amount;
-        
         emit!(TokensUnstaked {
             user: ctx.accounts.user.key(),
             amount,
-            remaining_staked: stake_account.staked_amount,
+            remaining_staked: ctx.accounts.stake_account.staked_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Fund the staking reward pool (e.g. from the ecosystem rewards allocation or protocol fees)
+    pub fn fund_staking_rewards(ctx: Context<FundStakingRewards>, amount: u64) -> Result<()> {
+        let cpi_accounts = anchor_spl::token::Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        anchor_spl::token::transfer(cpi_ctx, amount)?;
+        
+        let governance = &mut ctx.accounts.governance;
+        
+        // Bring the accumulator up to date so the new funds only emit from now on
+        accrue_staking_rewards(governance, Clock::get()?.unix_timestamp)?;
+        governance.reward_reserve = checked_add(governance.reward_reserve, amount)?;
+        
+        emit!(StakingRewardsFunded {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            reward_reserve: governance.reward_reserve,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Claim accrued staking rewards, optionally compounding them into stake
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>, compound: bool) -> Result<()> {
+        let amount = apply_claim(
+            &mut ctx.accounts.governance,
+            &mut ctx.accounts.stake_account,
+            compound,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        // Compounded rewards move into the stake vault, otherwise to the user
+        let destination = if compound {
+            ctx.accounts.governance_vault.to_account_info()
+        } else {
+            ctx.accounts.user_token_account.to_account_info()
+        };
+        
+        let seeds = &[seeds::GOVERNANCE, &[*ctx.bumps.get("governance").unwrap()]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = anchor_spl::token::Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: destination,
+            authority: ctx.accounts.governance.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        anchor_spl::token::transfer(cpi_ctx, amount)?;
+        
+        emit!(StakingRewardsClaimed {
+            user: ctx.accounts.user.key(),
+            amount,
+            compounded: compound,
+            total_staked: ctx.accounts.stake_account.staked_amount,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
         Ok(())
     }
+
+    /// Propose a fast-tracked operational action as a council member
+    pub fn propose_council_action(
+        ctx: Context<ProposeCouncilAction>,
+        execution_data: Vec<u8>,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        let council_action = &mut ctx.accounts.council_action;
+        let member = ctx.accounts.member.key();
+        
+        require!(
+            governance.council.members.contains(&member),
+            ErrorCode::NotCouncilMember
+        );
+        
+        require!(
+            execution_data.len() <= MAX_EXECUTION_DATA_LEN,
+            ErrorCode::ExecutionDataTooLarge
+        );
+        
+        let instructions = decode_instruction_bundle(&execution_data)?;
+        require!(!instructions.is_empty(), ErrorCode::InvalidExecutionData);
+        require!(
+            instructions.iter().all(is_council_action),
+            ErrorCode::ActionNotAllowedForCouncil
+        );
+        
+        council_action.id = governance.council_action_count;
+        council_action.proposer = member;
+        council_action.council_epoch = governance.council_epoch;
+        council_action.execution_data = execution_data;
+        council_action.approvals = vec![member]; // Proposing counts as approval
+        council_action.status = CouncilActionStatus::Pending;
+        council_action.created_at = Clock::get()?.unix_timestamp;
+        council_action.executed_at = 0;
+        council_action.execution_eta = 0;
+        let created_at = council_action.created_at;
+        schedule_council_action(council_action, &governance.council, created_at)?;
+        
+        governance.council_action_count = checked_add(governance.council_action_count, 1)?;
+        
+        emit!(CouncilActionProposed {
+            action_id: council_action.id,
+            proposer: member,
+            council_epoch: council_action.council_epoch,
+            timestamp: council_action.created_at,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a pending council action
+    pub fn approve_council_action(ctx: Context<ApproveCouncilAction>) -> Result<()> {
+        let council_action = &mut ctx.accounts.council_action;
+        let member = ctx.accounts.member.key();
+        let current_time = Clock::get()?.unix_timestamp;
+        
+        record_council_approval(&ctx.accounts.governance, council_action, member, current_time)?;
+        
+        emit!(CouncilActionApproved {
+            action_id: council_action.id,
+            member,
+            approvals: council_action.approvals.len() as u8,
+            execution_eta: council_action.execution_eta,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a council action once it has M-of-N approvals and its delay has passed
+    pub fn execute_council_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteCouncilAction<'info>>,
+    ) -> Result<()> {
+        let council_action = &ctx.accounts.council_action;
+        
+        check_council_action_executable(
+            &ctx.accounts.governance,
+            council_action,
+            Clock::get()?.unix_timestamp,
+        )?;
+        
+        let instructions = decode_instruction_bundle(&council_action.execution_data)?;
+        require!(
+            instructions.iter().all(is_council_action),
+            ErrorCode::ActionNotAllowedForCouncil
+        );
+        
+        execute_instruction_bundle(
+            &instructions,
+            ctx.accounts.governance.to_account_info(),
+            ctx.remaining_accounts,
+            *ctx.bumps.get("governance").unwrap(),
+            [ctx.accounts.governance.governance_vault, ctx.accounts.governance.reward_vault],
+        )?;
+        
+        let council_action = &mut ctx.accounts.council_action;
+        council_action.status = CouncilActionStatus::Executed;
+        council_action.executed_at = Clock::get()?.unix_timestamp;
+        
+        emit!(CouncilActionExecuted {
+            action_id: council_action.id,
+            approvals: council_action.approvals.len() as u8,
+            timestamp: council_action.executed_at,
+        });
+
+        Ok(())
+    }
+
+    /// Start handing the admin role to `new_authority`, which must accept it.
+    /// Proposing `Pubkey::default()` cancels a pending handover. Proposals cannot call
+    /// the governance program, so the governance PDA itself cannot take this role.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        
+        require!(
+            ctx.accounts.authority.key() == governance.authority,
+            CommonError::Unauthorized
+        );
+        
+        require!(new_authority != governance.key(), ErrorCode::InvalidAuthority);
+        
+        governance.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            scope: governance.key(),
+            authority: governance.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Complete an admin handover as the proposed key
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+        let new_authority = ctx.accounts.new_authority.key();
+        
+        require!(
+            governance.pending_authority != Pubkey::default()
+                && new_authority == governance.pending_authority,
+            CommonError::NotPendingAuthority
+        );
+        
+        let previous_authority = governance.authority;
+        governance.authority = new_authority;
+        governance.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            scope: governance.key(),
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+}
+
+/// Advance the global reward-per-token accumulator up to `now`.
+/// Emission is capped by the funded reserve and pauses while nothing is staked.
+fn accrue_staking_rewards(governance: &mut Governance, now: i64) -> Result<()> {
+    if now <= governance.last_reward_update {
+        return Ok(());
+    }
+    
+    if governance.total_staked > 0 && governance.reward_rate > 0 {
+        let elapsed = (now - governance.last_reward_update) as u128;
+        let emitted = (governance.reward_rate as u128)
+            .checked_mul(elapsed)
+            .ok_or(CommonError::MathOverflow)?
+            .min(governance.reward_reserve as u128);
+        
+        // Rounds down; only the amount credited to stakers leaves the reserve, so the
+        // remainder is emitted later. The credited amount rounds up so that claims,
+        // each rounded down, never add up to more than was taken from the reserve.
+        let per_token = math::mul_div(
+            emitted,
+            REWARD_PRECISION,
+            governance.total_staked as u128,
+            Rounding::Down,
+        )?;
+        let distributed = math::mul_div(
+            per_token,
+            governance.total_staked as u128,
+            REWARD_PRECISION,
+            Rounding::Up,
+        )?;
+        governance.reward_per_token_stored = governance
+            .reward_per_token_stored
+            .checked_add(per_token)
+            .ok_or(CommonError::MathOverflow)?;
+        governance.reward_reserve = checked_sub(governance.reward_reserve, to_u64(distributed)?)?;
+    }
+    
+    governance.last_reward_update = now;
+    Ok(())
+}
+
+/// Credit a stake account with rewards earned since its last checkpoint
+fn settle_stake_rewards(governance: &Governance, stake_account: &mut StakeAccount) -> Result<()> {
+    let delta = governance
+        .reward_per_token_stored
+        .checked_sub(stake_account.reward_per_token_paid)
+        .ok_or(CommonError::MathOverflow)?;
+    let earned = math::mul_div(
+        stake_account.staked_amount as u128,
+        delta,
+        REWARD_PRECISION,
+        Rounding::Down,
+    )?;
+    
+    stake_account.rewards_earned = checked_add(stake_account.rewards_earned, to_u64(earned)?)?;
+    stake_account.reward_per_token_paid = governance.reward_per_token_stored;
+    Ok(())
+}
+
+/// Add `amount` to a stake, settling rewards at the old balance first
+fn apply_stake(governance: &mut Governance, stake_account: &mut StakeAccount, amount: u64, now: i64) -> Result<()> {
+    accrue_staking_rewards(governance, now)?;
+    settle_stake_rewards(governance, stake_account)?;
+    
+    stake_account.staked_amount = checked_add(stake_account.staked_amount, amount)?;
+    stake_account.last_stake_time = now;
+    governance.total_staked = checked_add(governance.total_staked, amount)?;
+    Ok(())
+}
+
+/// Remove `amount` from an unlocked stake, settling rewards at the old balance first
+fn apply_unstake(governance: &mut Governance, stake_account: &mut StakeAccount, amount: u64, now: i64) -> Result<()> {
+    require!(now >= stake_account.voting_lock_until, ErrorCode::StakeLocked);
+    require!(stake_account.staked_amount >= amount, ErrorCode::InsufficientStakedAmount);
+    
+    accrue_staking_rewards(governance, now)?;
+    settle_stake_rewards(governance, stake_account)?;
+    
+    stake_account.staked_amount = checked_sub(stake_account.staked_amount, amount)?;
+    governance.total_staked = checked_sub(governance.total_staked, amount)?;
+    Ok(())
+}
+
+/// Settle and zero a stake's earned rewards, adding them to the stake when compounding.
+/// Returns the amount to move out of the reward vault.
+fn apply_claim(governance: &mut Governance, stake_account: &mut StakeAccount, compound: bool, now: i64) -> Result<u64> {
+    accrue_staking_rewards(governance, now)?;
+    settle_stake_rewards(governance, stake_account)?;
+    
+    let amount = stake_account.rewards_earned;
+    require!(amount > 0, ErrorCode::NoRewardsToClaim);
+    
+    stake_account.rewards_earned = 0;
+    if compound {
+        stake_account.staked_amount = checked_add(stake_account.staked_amount, amount)?;
+        stake_account.last_stake_time = now;
+        governance.total_staked = checked_add(governance.total_staked, amount)?;
+    }
+    Ok(amount)
+}
+
+/// Apply a passed parameter change proposal to the governance config
+fn apply_parameter_change(governance: &mut Governance, parameter: GovernanceParameter, now: i64) -> Result<()> {
+    match parameter {
+        GovernanceParameter::MinProposalStake(value) => {
+            governance.min_proposal_stake = value;
+        }
+        GovernanceParameter::VotingPeriod(value) => {
+            require!(value > 0, ErrorCode::InvalidParameterValue);
+            require_council_outlasts_proposals(&governance.council, value, governance.execution_delay)?;
+            governance.voting_period = value;
+        }
+        GovernanceParameter::ExecutionDelay(value) => {
+            require!(value >= 0, ErrorCode::InvalidParameterValue);
+            require_council_outlasts_proposals(&governance.council, governance.voting_period, value)?;
+            governance.execution_delay = value;
+        }
+        GovernanceParameter::StakingRewardRate(value) => {
+            // Emissions up to now accrue at the old rate
+            accrue_staking_rewards(governance, now)?;
+            governance.reward_rate = value;
+        }
+        GovernanceParameter::EmergencyConfig(ref config) => {
+            validate_emergency_config(config)?;
+            governance.emergency = config.clone();
+        }
+        GovernanceParameter::Council(ref config) => {
+            // Reconstituting the council invalidates all pending council actions
+            validate_council_config(config, governance.voting_period, governance.execution_delay)?;
+            governance.council = config.clone();
+            governance.council_epoch = governance
+                .council_epoch
+                .checked_add(1)
+                .ok_or(CommonError::MathOverflow)?;
+        }
+        GovernanceParameter::CancelCouncilAction(_) => {
+            // Applied by `execute_proposal`, which has the action account
+            return err!(ErrorCode::InvalidExecutionData);
+        }
+    }
+    
+    emit!(ParameterChanged {
+        parameter,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Emergency proposals must require a real supermajority and quorum
+fn validate_emergency_config(config: &EmergencyConfig) -> Result<()> {
+    require!(config.voting_period > 0, ErrorCode::InvalidParameterValue);
+    require!(config.quorum > 0, ErrorCode::InvalidParameterValue);
+    require!(
+        config.approval_threshold_bps > 5_000 && config.approval_threshold_bps <= 10_000,
+        ErrorCode::InvalidParameterValue
+    );
+    Ok(())
+}
+
+/// An empty council (threshold 0) disables the council entirely
+fn validate_council_config(config: &CouncilConfig, voting_period: i64, execution_delay: i64) -> Result<()> {
+    require!(
+        config.members.len() <= MAX_COUNCIL_MEMBERS,
+        ErrorCode::InvalidCouncilConfig
+    );
+    require!(
+        config.threshold as usize <= config.members.len(),
+        ErrorCode::InvalidCouncilConfig
+    );
+    require!(
+        config.members.is_empty() || config.threshold > 0,
+        ErrorCode::InvalidCouncilConfig
+    );
+    require!(config.execution_delay >= 0, ErrorCode::InvalidCouncilConfig);
+    require_council_outlasts_proposals(config, voting_period, execution_delay)?;
+    
+    for (i, member) in config.members.iter().enumerate() {
+        require!(
+            !config.members[i + 1..].contains(member),
+            ErrorCode::InvalidCouncilConfig
+        );
+    }
+    
+    Ok(())
+}
+
+/// Token holders veto a council action with a `CancelCouncilAction` proposal, so an
+/// active council must wait longer than such a proposal takes to vote and execute
+fn require_council_outlasts_proposals(council: &CouncilConfig, voting_period: i64, execution_delay: i64) -> Result<()> {
+    let proposal_duration = voting_period
+        .checked_add(execution_delay)
+        .ok_or(CommonError::MathOverflow)?;
+    require!(
+        council.members.is_empty() || council.execution_delay > proposal_duration,
+        ErrorCode::CouncilDelayTooShort
+    );
+    Ok(())
+}
+
+/// Add a member's approval to a pending council action of the current council
+fn record_council_approval(
+    governance: &Governance,
+    council_action: &mut CouncilAction,
+    member: Pubkey,
+    current_time: i64,
+) -> Result<()> {
+    require!(
+        governance.council.members.contains(&member),
+        ErrorCode::NotCouncilMember
+    );
+    
+    require!(
+        council_action.status == CouncilActionStatus::Pending,
+        ErrorCode::CouncilActionNotPending
+    );
+    
+    // Actions proposed before the council was reconstituted cannot be approved
+    require!(
+        council_action.council_epoch == governance.council_epoch,
+        ErrorCode::StaleCouncilAction
+    );
+    
+    require!(
+        !council_action.approvals.contains(&member),
+        ErrorCode::AlreadyApproved
+    );
+    
+    council_action.approvals.push(member);
+    schedule_council_action(council_action, &governance.council, current_time)
+}
+
+/// A council action may run once it has M-of-N approvals of the current council
+/// and its execution delay has passed
+fn check_council_action_executable(
+    governance: &Governance,
+    council_action: &CouncilAction,
+    current_time: i64,
+) -> Result<()> {
+    require!(
+        council_action.status == CouncilActionStatus::Pending,
+        ErrorCode::CouncilActionNotPending
+    );
+    
+    require!(
+        council_action.council_epoch == governance.council_epoch,
+        ErrorCode::StaleCouncilAction
+    );
+    
+    require!(
+        council_action.approvals.len() >= governance.council.threshold as usize,
+        ErrorCode::InsufficientApprovals
+    );
+    
+    // Token holders can cancel the action through a proposal until the delay passes
+    require!(
+        council_action.execution_eta > 0 && current_time >= council_action.execution_eta,
+        ErrorCode::ExecutionDelayNotPassed
+    );
+    Ok(())
+}
+
+/// Veto a pending council action on behalf of a passed `CancelCouncilAction` proposal
+fn cancel_council_action(council_action: &mut CouncilAction, action_id: u64) -> Result<()> {
+    require!(council_action.id == action_id, ErrorCode::InvalidExecutionData);
+    require!(
+        council_action.status == CouncilActionStatus::Pending,
+        ErrorCode::CouncilActionNotPending
+    );
+    
+    council_action.status = CouncilActionStatus::Cancelled;
+    Ok(())
+}
+
+/// Start the execution delay once a council action first reaches the approval threshold
+fn schedule_council_action(
+    council_action: &mut CouncilAction,
+    council: &CouncilConfig,
+    current_time: i64,
+) -> Result<()> {
+    if council_action.execution_eta == 0
+        && council_action.approvals.len() >= council.threshold as usize
+    {
+        council_action.execution_eta = current_time
+            .checked_add(council.execution_delay)
+            .ok_or(CommonError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+/// Enforces the bounds `Proposal::space` relies on
+fn validate_proposal_metadata(
+    title: &str,
+    uri: &str,
+    content_hash: &[u8; 32],
+    execution_data: &[u8],
+) -> Result<()> {
+    require!(!title.is_empty(), ErrorCode::EmptyTitle);
+    require!(title.len() <= MAX_TITLE_LEN, ErrorCode::TitleTooLong);
+    require!(uri.len() <= MAX_URI_LEN, ErrorCode::UriTooLong);
+    require!(*content_hash != [0u8; 32], ErrorCode::MissingContentHash);
+    require!(
+        execution_data.len() <= MAX_EXECUTION_DATA_LEN,
+        ErrorCode::ExecutionDataTooLarge
+    );
+    Ok(())
+}
+
+/// Whether an emergency vote reached quorum and the supermajority threshold
+fn emergency_vote_passed(config: &EmergencyConfig, votes_for: u64, votes_against: u64) -> bool {
+    let total_votes = votes_for as u128 + votes_against as u128;
+    total_votes >= config.quorum as u128
+        && votes_for as u128 * 10_000 >= total_votes * config.approval_threshold_bps as u128
+}
+
+fn decode_instruction_bundle(execution_data: &[u8]) -> Result<Vec<ProposalInstruction>> {
+    Vec::<ProposalInstruction>::try_from_slice(execution_data)
+        .map_err(|_| ErrorCode::InvalidExecutionData.into())
+}
+
+/// Emergency proposals may only pause or resume yield pools and
+/// freeze or reactivate property purchases
+fn is_emergency_action(instruction: &ProposalInstruction) -> bool {
+    if instruction.data.len() < 8 {
+        return false;
+    }
+    let (discriminator, args) = instruction.data.split_at(8);
+    
+    if instruction.program_id == yield_distributor::ID {
+        return discriminator == yield_distributor::instruction::PausePool::DISCRIMINATOR
+            || discriminator == yield_distributor::instruction::ResumePool::DISCRIMINATOR;
+    }
+    
+    if instruction.program_id == property_vault::ID
+        && discriminator == property_vault::instruction::UpdatePropertyStatus::DISCRIMINATOR
+    {
+        return matches!(
+            property_vault::PropertyStatus::try_from_slice(args),
+            Ok(property_vault::PropertyStatus::Active) | Ok(property_vault::PropertyStatus::Paused)
+        );
+    }
+    
+    false
+}
+
+/// The council may fast-track emergency actions plus property status changes.
+/// Liquidation is left to the property's token holders.
+fn is_council_action(instruction: &ProposalInstruction) -> bool {
+    if is_emergency_action(instruction) {
+        return true;
+    }
+    
+    instruction.program_id == property_vault::ID
+        && instruction.data.len() >= 8
+        && instruction.data[..8] == property_vault::instruction::UpdatePropertyStatus::DISCRIMINATOR
+        && matches!(
+            property_vault::PropertyStatus::try_from_slice(&instruction.data[8..]),
+            Ok(status) if status != property_vault::PropertyStatus::Liquidating
+        )
+}
+
+/// Whether an SPL Token instruction acts on one of the governance vaults as its
+/// source account (transfer, approve, burn, close, set authority, freeze)
+fn spends_protected_vault(instruction: &ProposalInstruction, protected_vaults: &[Pubkey]) -> bool {
+    instruction.program_id == anchor_spl::token::ID
+        && matches!(
+            instruction.accounts.first(),
+            Some(source) if protected_vaults.contains(&source.pubkey)
+        )
+}
+
+/// Invoke each instruction with the governance PDA as signer.
+/// Target accounts are supplied through `remaining_accounts`.
+fn execute_instruction_bundle<'info>(
+    instructions: &[ProposalInstruction],
+    governance: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    governance_bump: u8,
+    protected_vaults: [Pubkey; 2],
+) -> Result<()> {
+    let seeds = &[seeds::GOVERNANCE, &[governance_bump]];
+    let signer = &[&seeds[..]];
+    
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(governance);
+    
+    for instruction in instructions {
+        // Governance state is only changed through parameter change proposals
+        require!(
+            instruction.program_id != crate::ID,
+            ErrorCode::InvalidExecutionData
+        );
+        
+        // Staked and reward tokens only leave through unstake and claim
+        require!(
+            !spends_protected_vault(instruction, &protected_vaults),
+            ErrorCode::ProtectedVault
+        );
+        
+        let ix = Instruction {
+            program_id: instruction.program_id,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        };
+        invoke_signed(&ix, &account_infos, signer)?;
+    }
+    
+    Ok(())
 }
 
 #[derive(Accounts)]
@@ -268,13 +1016,26 @@ pub struct InitializeGovernance<'info> {
         init,
         payer = authority,
         space = 8 + Governance::LEN,
-        seeds = [b"governance"],
+        seeds = [seeds::GOVERNANCE],
         bump
     )]
     pub governance: Account<'info, Governance>,
     
     pub arkly_mint: Account<'info, anchor_spl::token::Mint>,
     
+    #[account(
+        constraint = governance_vault.mint == arkly_mint.key() @ ErrorCode::InvalidVault,
+        constraint = governance_vault.owner == governance.key() @ ErrorCode::InvalidVault
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = reward_vault.mint == arkly_mint.key() @ ErrorCode::InvalidVault,
+        constraint = reward_vault.owner == governance.key() @ ErrorCode::InvalidVault,
+        constraint = reward_vault.key() != governance_vault.key() @ ErrorCode::InvalidVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -282,7 +1043,7 @@ pub struct InitializeGovernance<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(title: String)]
+#[instruction(title: String, uri: String, content_hash: [u8; 32], proposal_type: ProposalType, execution_data: Vec<u8>)]
 pub struct CreateProposal<'info> {
     #[account(mut)]
     pub governance: Account<'info, Governance>,
@@ -290,8 +1051,8 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = 8 + Proposal::LEN,
-        seeds = [b"proposal", governance.proposal_count.to_le_bytes().as_ref()],
+        space = 8 + Proposal::space(&title, &uri, &execution_data),
+        seeds = [seeds::PROPOSAL, governance.proposal_count.to_le_bytes().as_ref()],
         bump
     )]
     pub proposal: Account<'info, Proposal>,
@@ -306,6 +1067,12 @@ pub struct CreateProposal<'info> {
 
 #[derive(Accounts)]
 pub struct Vote<'info> {
+    #[account(
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     
@@ -313,12 +1080,17 @@ pub struct Vote<'info> {
         init_if_needed,
         payer = voter,
         space = 8 + VoterRecord::LEN,
-        seeds = [b"voter_record", proposal.key().as_ref(), voter.key().as_ref()],
+        seeds = [seeds::VOTER_RECORD, proposal.key().as_ref(), voter.key().as_ref()],
         bump
     )]
     pub voter_record: Account<'info, VoterRecord>,
     
-    pub voter_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [seeds::STAKE, voter.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
     
     #[account(mut)]
     pub voter: Signer<'info>,
@@ -336,6 +1108,13 @@ pub struct QueueProposal<'info> {
 
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
 }
@@ -349,7 +1128,7 @@ pub struct StakeTokens<'info> {
         init_if_needed,
         payer = user,
         space = 8 + StakeAccount::LEN,
-        seeds = [b"stake", user.key().as_ref()],
+        seeds = [seeds::STAKE, user.key().as_ref()],
         bump
     )]
     pub stake_account: Account<'info, StakeAccount>,
@@ -357,7 +1136,7 @@ pub struct StakeTokens<'info> {
     #[account(mut)]
     pub user_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, address = governance.governance_vault @ ErrorCode::InvalidVault)]
     pub governance_vault: Account<'info, TokenAccount>,
     
     #[account(mut)]
@@ -371,18 +1150,22 @@ pub struct StakeTokens<'info> {
 pub struct UnstakeTokens<'info> {
     #[account(
         mut,
-        seeds = [b"governance"],
+        seeds = [seeds::GOVERNANCE],
         bump
     )]
     pub governance: Account<'info, Governance>,
     
-    #[account(mut)]
-    pub stake_account: Account<'info, StakeAccount>,
-    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE, user.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    
     #[account(mut)]
     pub user_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, address = governance.governance_vault @ ErrorCode::InvalidVault)]
     pub governance_vault: Account<'info, TokenAccount>,
     
     #[account(mut)]
@@ -391,6 +1174,129 @@ pub struct UnstakeTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct FundStakingRewards<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(mut)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = governance.reward_vault @ ErrorCode::InvalidVault)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::STAKE, user.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = governance.governance_vault @ ErrorCode::InvalidVault)]
+    pub governance_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = governance.reward_vault @ ErrorCode::InvalidVault)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeCouncilAction<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(
+        init,
+        payer = member,
+        space = 8 + CouncilAction::LEN,
+        seeds = [seeds::COUNCIL_ACTION, governance.council_action_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub council_action: Account<'info, CouncilAction>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveCouncilAction<'info> {
+    #[account(
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
+    
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteCouncilAction<'info> {
+    #[account(
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::GOVERNANCE],
+        bump
+    )]
+    pub governance: Account<'info, Governance>,
+    
+    /// The pending authority
+    pub new_authority: Signer<'info>,
+}
+
 #[account]
 pub struct Governance {
     pub authority: Pubkey,
@@ -400,18 +1306,85 @@ pub struct Governance {
     pub execution_delay: i64,
     pub proposal_count: u64,
     pub total_staked: u64,
+    pub governance_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reward_rate: u64, // ARKLY base units emitted per second across all stakers
+    pub reward_reserve: u64, // Funded rewards not yet emitted
+    pub reward_per_token_stored: u128, // Scaled by REWARD_PRECISION
+    pub last_reward_update: i64,
+    pub emergency: EmergencyConfig,
+    pub council: CouncilConfig,
+    pub council_epoch: u32, // Incremented each time the DAO reconstitutes the council
+    pub council_action_count: u64,
+    pub pending_authority: Pubkey, // Default unless a handover is in progress
 }
 
 impl Governance {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8;
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 16 + 8
+        + EmergencyConfig::LEN + CouncilConfig::LEN + 4 + 8
+        + 32;
+}
+
+/// Voting rules for `ProposalType::Emergency`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct EmergencyConfig {
+    pub voting_period: i64,
+    pub quorum: u64, // Minimum total votes cast
+    pub approval_threshold_bps: u16, // Share of votes cast that must be in favour
+}
+
+impl EmergencyConfig {
+    pub const LEN: usize = 8 + 8 + 2;
+}
+
+pub const MAX_COUNCIL_MEMBERS: usize = 9;
+
+/// M-of-N council able to fast-track whitelisted operational actions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CouncilConfig {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub execution_delay: i64, // Seconds between reaching the threshold and execution
+}
+
+impl CouncilConfig {
+    pub const LEN: usize = 4 + 32 * MAX_COUNCIL_MEMBERS + 1 + 8;
+}
+
+#[account]
+pub struct CouncilAction {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub council_epoch: u32,
+    pub execution_data: Vec<u8>,
+    pub approvals: Vec<Pubkey>,
+    pub status: CouncilActionStatus,
+    pub created_at: i64,
+    pub executed_at: i64,
+    pub execution_eta: i64, // 0 until the approval threshold is reached
 }
 
+impl CouncilAction {
+    pub const LEN: usize = 8 + 32 + 4 + 4 + MAX_EXECUTION_DATA_LEN + 4 + 32 * MAX_COUNCIL_MEMBERS + 1 + 8 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum CouncilActionStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+/// Fixed-point scale for the reward-per-token accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
 #[account]
 pub struct Proposal {
     pub id: u64,
     pub proposer: Pubkey,
     pub title: String,
-    pub description: String,
+    pub uri: String,
+    pub content_hash: [u8; 32], // sha256 of the off-chain proposal document
     pub proposal_type: ProposalType,
     pub execution_data: Vec<u8>,
     pub votes_for: u64,
@@ -423,15 +1396,53 @@ pub struct Proposal {
 }
 
 impl Proposal {
-    pub const LEN: usize = 8 + 32 + 4 + 100 + 4 + 500 + 1 + 4 + 256 + 8 + 8 + 1 + 8 + 8 + 8;
+    /// Size of everything except the variable-length title, URI and execution data
+    pub const BASE_LEN: usize = 8 + 32 + 4 + 4 + 32 + 1 + 4 + 8 + 8 + 1 + 8 + 8 + 8;
+    
+    /// Proposals are sized to their actual content so rent is not paid on unused space
+    pub fn space(title: &str, uri: &str, execution_data: &[u8]) -> usize {
+        Self::BASE_LEN + title.len() + uri.len() + execution_data.len()
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_URI_LEN: usize = 200;
+pub const MAX_EXECUTION_DATA_LEN: usize = 256;
+
+/// A single instruction in a proposal's execution bundle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum ProposalType {
     ParameterChange,
     TreasurySpend,
     ProtocolUpgrade,
     PropertyListing,
+    Emergency, // Whitelisted pause/unpause actions only
+}
+
+/// Borsh-encoded in `Proposal.execution_data` for `ParameterChange` proposals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum GovernanceParameter {
+    MinProposalStake(u64),
+    VotingPeriod(i64),
+    ExecutionDelay(i64),
+    StakingRewardRate(u64),
+    EmergencyConfig(EmergencyConfig),
+    Council(CouncilConfig),
+    CancelCouncilAction(u64), // Veto a pending council action by ID
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -462,10 +1473,13 @@ pub struct StakeAccount {
     pub user: Pubkey,
     pub staked_amount: u64,
     pub last_stake_time: i64,
+    pub reward_per_token_paid: u128,
+    pub rewards_earned: u64,
+    pub voting_lock_until: i64, // Unstaking is blocked until the last voted proposal closes
 }
 
 impl StakeAccount {
-    pub const LEN: usize = 32 + 8 + 8;
+    pub const LEN: usize = 32 + 8 + 8 + 16 + 8 + 8;
 }
 
 #[event]
@@ -473,6 +1487,8 @@ pub struct ProposalCreated {
     pub proposal_id: u64,
     pub proposer: Pubkey,
     pub title: String,
+    pub uri: String,
+    pub content_hash: [u8; 32],
     pub proposal_type: ProposalType,
     pub voting_ends_at: i64,
 }
@@ -519,6 +1535,60 @@ pub struct TokensUnstaked {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct StakingRewardsFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub reward_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingRewardsClaimed {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub compounded: bool,
+    pub total_staked: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParameterChanged {
+    pub parameter: GovernanceParameter,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilActionProposed {
+    pub action_id: u64,
+    pub proposer: Pubkey,
+    pub council_epoch: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilActionApproved {
+    pub action_id: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+    pub execution_eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilActionCancelled {
+    pub action_id: u64,
+    pub proposal_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouncilActionExecuted {
+    pub action_id: u64,
+    pub approvals: u8,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Insufficient stake to create proposal")]
@@ -537,4 +1607,585 @@ pub enum ErrorCode {
     ExecutionDelayNotPassed,
     #[msg("Insufficient staked amount")]
     InsufficientStakedAmount,
+    #[msg("Invalid vault account")]
+    InvalidVault,
+    #[msg("No staking rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Invalid proposal execution data")]
+    InvalidExecutionData,
+    #[msg("Invalid parameter value")]
+    InvalidParameterValue,
+    #[msg("Proposal execution data is too large")]
+    ExecutionDataTooLarge,
+    #[msg("Action is not allowed in an emergency proposal")]
+    ActionNotAllowedForEmergency,
+    #[msg("Invalid council configuration")]
+    InvalidCouncilConfig,
+    #[msg("Signer is not a council member")]
+    NotCouncilMember,
+    #[msg("Action is not allowed for the council")]
+    ActionNotAllowedForCouncil,
+    #[msg("Council action is not pending")]
+    CouncilActionNotPending,
+    #[msg("Council action was proposed by a previous council")]
+    StaleCouncilAction,
+    #[msg("Council member has already approved this action")]
+    AlreadyApproved,
+    #[msg("Not enough council approvals")]
+    InsufficientApprovals,
+    #[msg("Proposal title is empty")]
+    EmptyTitle,
+    #[msg("Proposal title exceeds 64 bytes")]
+    TitleTooLong,
+    #[msg("Proposal URI exceeds 200 bytes")]
+    UriTooLong,
+    #[msg("Proposal content hash is missing")]
+    MissingContentHash,
+    #[msg("Stake is locked until the proposals it voted on close")]
+    StakeLocked,
+    #[msg("Instructions may not spend from the stake or reward vault")]
+    ProtectedVault,
+    #[msg("Council execution delay must outlast a governance vote and timelock")]
+    CouncilDelayTooShort,    #[msg("Governance cannot hold its own admin role")]
+    InvalidAuthority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_700_000_000;
+
+    fn governance(total_staked: u64, reward_rate: u64, reward_reserve: u64) -> Governance {
+        Governance {
+            authority: Pubkey::new_unique(),
+            arkly_mint: Pubkey::new_unique(),
+            min_proposal_stake: 0,
+            voting_period: 0,
+            execution_delay: 0,
+            proposal_count: 0,
+            total_staked,
+            governance_vault: Pubkey::new_unique(),
+            reward_vault: Pubkey::new_unique(),
+            reward_rate,
+            reward_reserve,
+            reward_per_token_stored: 0,
+            last_reward_update: START,
+            emergency: EmergencyConfig { voting_period: 0, quorum: 0, approval_threshold_bps: 0 },
+            council: CouncilConfig { members: Vec::new(), threshold: 0, execution_delay: 0 },
+            council_epoch: 0,
+            council_action_count: 0,
+            pending_authority: Pubkey::default(),
+        }
+    }
+
+    fn stake_account(staked_amount: u64, reward_per_token_paid: u128) -> StakeAccount {
+        StakeAccount {
+            user: Pubkey::new_unique(),
+            staked_amount,
+            last_stake_time: START,
+            reward_per_token_paid,
+            rewards_earned: 0,
+            voting_lock_until: 0,
+        }
+    }
+
+    #[test]
+    fn only_the_credited_rewards_leave_the_reserve() {
+        let precision = REWARD_PRECISION as u64;
+        let mut governance = governance(3 * precision, 10, 100);
+        let mut stakers = [stake_account(precision, 0), stake_account(2 * precision, 0)];
+
+        // 10 emitted over 3 * PRECISION staked credits 3 per PRECISION tokens: 9 in all
+        accrue_staking_rewards(&mut governance, START + 1).unwrap();
+        assert_eq!(governance.reward_per_token_stored, 3);
+        assert_eq!(governance.reward_reserve, 91);
+
+        accrue_staking_rewards(&mut governance, START + 2).unwrap();
+        for staker in stakers.iter_mut() {
+            settle_stake_rewards(&governance, staker).unwrap();
+        }
+        assert_eq!((stakers[0].rewards_earned, stakers[1].rewards_earned), (6, 12));
+        assert_eq!(governance.reward_reserve, 100 - 18);
+    }
+
+    #[test]
+    fn claims_never_exceed_what_left_the_reserve() {
+        let mut governance = governance(3, 1, 100);
+        let mut stakers = [stake_account(1, 0), stake_account(2, 0)];
+
+        // A third of a unit per token is credited; the unit it rounds up to leaves the reserve
+        accrue_staking_rewards(&mut governance, START + 1).unwrap();
+        assert_eq!(governance.reward_per_token_stored, REWARD_PRECISION / 3);
+        assert_eq!(governance.reward_reserve, 99);
+
+        accrue_staking_rewards(&mut governance, START + 3).unwrap();
+        for staker in stakers.iter_mut() {
+            settle_stake_rewards(&governance, staker).unwrap();
+        }
+        let claimed = stakers.iter().map(|staker| staker.rewards_earned).sum::<u64>();
+        assert_eq!((stakers[0].rewards_earned, stakers[1].rewards_earned), (0, 1));
+        assert!(claimed <= 100 - governance.reward_reserve);
+    }
+
+    #[test]
+    fn emission_is_capped_by_the_reserve() {
+        let mut governance = governance(1_000, 10, 50);
+
+        accrue_staking_rewards(&mut governance, START + 100).unwrap();
+
+        assert_eq!(governance.reward_reserve, 0);
+        assert_eq!(governance.reward_per_token_stored, 50 * REWARD_PRECISION / 1_000);
+        assert_eq!(governance.last_reward_update, START + 100);
+    }
+
+    #[test]
+    fn nothing_accrues_without_stakers_or_elapsed_time() {
+        let mut governance = governance(0, 10, 1_000);
+
+        accrue_staking_rewards(&mut governance, START + 100).unwrap();
+        assert_eq!(governance.reward_per_token_stored, 0);
+        assert_eq!(governance.reward_reserve, 1_000);
+        assert_eq!(governance.last_reward_update, START + 100);
+
+        accrue_staking_rewards(&mut governance, START + 50).unwrap();
+        assert_eq!(governance.last_reward_update, START + 100);
+    }
+
+    #[test]
+    fn accumulator_reports_overflow() {
+        let overflow = Error::from(CommonError::MathOverflow);
+
+        let mut governance = governance(1, 1, 100);
+        governance.reward_per_token_stored = u128::MAX;
+        assert_eq!(accrue_staking_rewards(&mut governance, START + 1).unwrap_err(), overflow);
+
+        let mut staker = stake_account(1, 1);
+        governance.reward_per_token_stored = 0;
+        assert_eq!(settle_stake_rewards(&governance, &mut staker).unwrap_err(), overflow);
+
+        let mut staker = stake_account(u64::MAX, 0);
+        governance.reward_per_token_stored = 2 * REWARD_PRECISION;
+        assert_eq!(settle_stake_rewards(&governance, &mut staker).unwrap_err(), overflow);
+    }
+
+    #[test]
+    fn late_stakers_share_only_rewards_emitted_after_they_stake() {
+        let mut governance = governance(0, 10, 1_000_000);
+        let mut early = stake_account(0, 0);
+        let mut late = stake_account(0, 0);
+
+        apply_stake(&mut governance, &mut early, 100, START).unwrap();
+        apply_stake(&mut governance, &mut late, 300, START + 10).unwrap();
+        assert_eq!(late.rewards_earned, 0);
+        assert_eq!(late.reward_per_token_paid, governance.reward_per_token_stored);
+
+        let claimed = (
+            apply_claim(&mut governance, &mut early, false, START + 20).unwrap(),
+            apply_claim(&mut governance, &mut late, false, START + 20).unwrap(),
+        );
+
+        assert_eq!(claimed, (125, 75));
+        assert_eq!(governance.reward_reserve, 1_000_000 - 200);
+        assert_eq!(governance.total_staked, 400);
+    }
+
+    #[test]
+    fn unstaking_settles_rewards_at_the_old_balance() {
+        let mut governance = governance(0, 10, 1_000_000);
+        let mut alice = stake_account(0, 0);
+        let mut bob = stake_account(0, 0);
+        apply_stake(&mut governance, &mut alice, 100, START).unwrap();
+        apply_stake(&mut governance, &mut bob, 100, START).unwrap();
+
+        apply_unstake(&mut governance, &mut alice, 50, START + 10).unwrap();
+        assert_eq!(alice.rewards_earned, 50);
+        assert_eq!(alice.staked_amount, 50);
+        assert_eq!(governance.total_staked, 150);
+
+        let claimed = (
+            apply_claim(&mut governance, &mut alice, false, START + 20).unwrap(),
+            apply_claim(&mut governance, &mut bob, false, START + 20).unwrap(),
+        );
+
+        // 100 emitted while the stake was split 1:2; rounding leaves 1 unit of dust
+        assert_eq!(claimed, (50 + 33, 50 + 66));
+        assert!(claimed.0 + claimed.1 <= 200);
+    }
+
+    #[test]
+    fn compounding_moves_rewards_into_the_stake() {
+        let mut governance = governance(0, 10, 1_000_000);
+        let mut staker = stake_account(0, 0);
+        apply_stake(&mut governance, &mut staker, 100, START).unwrap();
+
+        assert_eq!(apply_claim(&mut governance, &mut staker, true, START + 10).unwrap(), 100);
+        assert_eq!(staker.rewards_earned, 0);
+        assert_eq!(staker.staked_amount, 200);
+        assert_eq!(staker.last_stake_time, START + 10);
+        assert_eq!(governance.total_staked, 200);
+
+        assert_eq!(
+            apply_claim(&mut governance, &mut staker, true, START + 10).unwrap_err(),
+            ErrorCode::NoRewardsToClaim.into()
+        );
+
+        // The compounded stake earns alongside a new staker from now on
+        let mut other = stake_account(0, 0);
+        apply_stake(&mut governance, &mut other, 200, START + 10).unwrap();
+        assert_eq!(apply_claim(&mut governance, &mut staker, false, START + 20).unwrap(), 50);
+        assert_eq!(apply_claim(&mut governance, &mut other, false, START + 20).unwrap(), 50);
+    }
+
+    #[test]
+    fn unstaking_requires_an_unlocked_sufficient_stake() {
+        let mut governance = governance(0, 10, 1_000_000);
+        let mut staker = stake_account(0, 0);
+        apply_stake(&mut governance, &mut staker, 100, START).unwrap();
+        staker.voting_lock_until = START + 50;
+
+        assert_eq!(
+            apply_unstake(&mut governance, &mut staker, 100, START + 49).unwrap_err(),
+            ErrorCode::StakeLocked.into()
+        );
+        assert_eq!(
+            apply_unstake(&mut governance, &mut staker, 101, START + 50).unwrap_err(),
+            ErrorCode::InsufficientStakedAmount.into()
+        );
+
+        apply_unstake(&mut governance, &mut staker, 100, START + 50).unwrap();
+        assert_eq!(staker.rewards_earned, 500);
+        assert_eq!(governance.total_staked, 0);
+
+        // Nothing is emitted while the pool is empty
+        accrue_staking_rewards(&mut governance, START + 100).unwrap();
+        assert_eq!(governance.reward_reserve, 1_000_000 - 500);
+    }
+
+    const DAY: i64 = 86_400;
+
+    fn council_governance(members: &[Pubkey], threshold: u8) -> Governance {
+        let mut governance = governance(0, 0, 0);
+        governance.voting_period = 3 * DAY;
+        governance.execution_delay = 2 * DAY;
+        governance.council = CouncilConfig {
+            members: members.to_vec(),
+            threshold,
+            execution_delay: 6 * DAY,
+        };
+        governance
+    }
+
+    fn council_action(governance: &Governance, proposer: Pubkey, now: i64) -> CouncilAction {
+        let mut action = CouncilAction {
+            id: 7,
+            proposer,
+            council_epoch: governance.council_epoch,
+            execution_data: Vec::new(),
+            approvals: vec![proposer],
+            status: CouncilActionStatus::Pending,
+            created_at: now,
+            executed_at: 0,
+            execution_eta: 0,
+        };
+        schedule_council_action(&mut action, &governance.council, now).unwrap();
+        action
+    }
+
+    #[test]
+    fn council_delay_must_outlast_a_cancelling_proposal() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut council = CouncilConfig { members: members.to_vec(), threshold: 2, execution_delay: 5 * DAY };
+
+        assert_eq!(
+            validate_council_config(&council, 3 * DAY, 2 * DAY).unwrap_err(),
+            ErrorCode::CouncilDelayTooShort.into()
+        );
+        council.execution_delay = 5 * DAY + 1;
+        validate_council_config(&council, 3 * DAY, 2 * DAY).unwrap();
+
+        // A disabled council needs no delay
+        let disabled = CouncilConfig { members: Vec::new(), threshold: 0, execution_delay: 0 };
+        validate_council_config(&disabled, 3 * DAY, 2 * DAY).unwrap();
+
+        // Lengthening the vote or the timelock may not overtake the council delay
+        let mut governance = council_governance(&members, 2);
+        assert_eq!(
+            apply_parameter_change(&mut governance, GovernanceParameter::VotingPeriod(4 * DAY), START).unwrap_err(),
+            ErrorCode::CouncilDelayTooShort.into()
+        );
+        assert_eq!(
+            apply_parameter_change(&mut governance, GovernanceParameter::ExecutionDelay(3 * DAY), START).unwrap_err(),
+            ErrorCode::CouncilDelayTooShort.into()
+        );
+        apply_parameter_change(&mut governance, GovernanceParameter::VotingPeriod(4 * DAY - 1), START).unwrap();
+        assert_eq!(governance.voting_period, 4 * DAY - 1);
+    }
+
+    #[test]
+    fn council_config_rejects_bad_thresholds_and_duplicates() {
+        let member = Pubkey::new_unique();
+        let invalid = Error::from(ErrorCode::InvalidCouncilConfig);
+        let config = |members: Vec<Pubkey>, threshold| CouncilConfig { members, threshold, execution_delay: 6 * DAY };
+
+        assert_eq!(validate_council_config(&config(vec![member], 2), 3 * DAY, 2 * DAY).unwrap_err(), invalid);
+        assert_eq!(validate_council_config(&config(vec![member], 0), 3 * DAY, 2 * DAY).unwrap_err(), invalid);
+        assert_eq!(
+            validate_council_config(&config(vec![member, member], 1), 3 * DAY, 2 * DAY).unwrap_err(),
+            invalid
+        );
+        assert_eq!(
+            validate_council_config(&config(vec![member; MAX_COUNCIL_MEMBERS + 1], 1), 3 * DAY, 2 * DAY).unwrap_err(),
+            invalid
+        );
+        validate_council_config(&config(vec![member], 1), 3 * DAY, 2 * DAY).unwrap();
+    }
+
+    #[test]
+    fn council_actions_wait_for_the_threshold_and_the_delay() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let governance = council_governance(&members, 2);
+        let mut action = council_action(&governance, members[0], START);
+
+        assert_eq!(action.execution_eta, 0);
+        assert_eq!(
+            check_council_action_executable(&governance, &action, START + 30 * DAY).unwrap_err(),
+            ErrorCode::InsufficientApprovals.into()
+        );
+        assert_eq!(
+            record_council_approval(&governance, &mut action, members[0], START).unwrap_err(),
+            ErrorCode::AlreadyApproved.into()
+        );
+        assert_eq!(
+            record_council_approval(&governance, &mut action, Pubkey::new_unique(), START).unwrap_err(),
+            ErrorCode::NotCouncilMember.into()
+        );
+
+        // The delay starts when the threshold is reached and later approvals do not move it
+        record_council_approval(&governance, &mut action, members[1], START + DAY).unwrap();
+        let eta = START + DAY + 6 * DAY;
+        assert_eq!(action.execution_eta, eta);
+        record_council_approval(&governance, &mut action, members[2], START + 2 * DAY).unwrap();
+        assert_eq!(action.execution_eta, eta);
+
+        assert_eq!(
+            check_council_action_executable(&governance, &action, eta - 1).unwrap_err(),
+            ErrorCode::ExecutionDelayNotPassed.into()
+        );
+        check_council_action_executable(&governance, &action, eta).unwrap();
+    }
+
+    #[test]
+    fn reconstituting_the_council_invalidates_pending_actions() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut governance = council_governance(&members, 2);
+        let mut action = council_action(&governance, members[0], START);
+
+        let council = CouncilConfig { members: members.to_vec(), threshold: 1, execution_delay: 6 * DAY };
+        apply_parameter_change(&mut governance, GovernanceParameter::Council(council), START).unwrap();
+        assert_eq!(governance.council_epoch, 1);
+
+        assert_eq!(
+            record_council_approval(&governance, &mut action, members[1], START + DAY).unwrap_err(),
+            ErrorCode::StaleCouncilAction.into()
+        );
+        assert_eq!(
+            check_council_action_executable(&governance, &action, START + 30 * DAY).unwrap_err(),
+            ErrorCode::StaleCouncilAction.into()
+        );
+    }
+
+    #[test]
+    fn holders_cancel_a_pending_council_action() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let governance = council_governance(&members, 2);
+        let mut action = council_action(&governance, members[0], START);
+        record_council_approval(&governance, &mut action, members[1], START).unwrap();
+
+        assert_eq!(
+            cancel_council_action(&mut action, 8).unwrap_err(),
+            ErrorCode::InvalidExecutionData.into()
+        );
+        cancel_council_action(&mut action, 7).unwrap();
+        assert!(action.status == CouncilActionStatus::Cancelled);
+
+        assert_eq!(
+            check_council_action_executable(&governance, &action, action.execution_eta).unwrap_err(),
+            ErrorCode::CouncilActionNotPending.into()
+        );
+        assert_eq!(
+            cancel_council_action(&mut action, 7).unwrap_err(),
+            ErrorCode::CouncilActionNotPending.into()
+        );
+    }
+
+    fn instruction(program_id: Pubkey, accounts: &[Pubkey], data: Vec<u8>) -> ProposalInstruction {
+        ProposalInstruction {
+            program_id,
+            accounts: accounts
+                .iter()
+                .map(|pubkey| ProposalAccountMeta { pubkey: *pubkey, is_signer: false, is_writable: true })
+                .collect(),
+            data,
+        }
+    }
+
+    fn anchor_data(discriminator: [u8; 8], args: &[u8]) -> Vec<u8> {
+        [&discriminator[..], args].concat()
+    }
+
+    fn status_change(status: property_vault::PropertyStatus) -> ProposalInstruction {
+        instruction(
+            property_vault::ID,
+            &[Pubkey::new_unique()],
+            anchor_data(
+                property_vault::instruction::UpdatePropertyStatus::DISCRIMINATOR,
+                &status.try_to_vec().unwrap(),
+            ),
+        )
+    }
+
+    #[test]
+    fn emergencies_may_only_pause_and_resume() {
+        use property_vault::PropertyStatus;
+        let pool = [Pubkey::new_unique()];
+
+        let pause = anchor_data(yield_distributor::instruction::PausePool::DISCRIMINATOR, &[]);
+        let resume = anchor_data(yield_distributor::instruction::ResumePool::DISCRIMINATOR, &[]);
+        assert!(is_emergency_action(&instruction(yield_distributor::ID, &pool, pause.clone())));
+        assert!(is_emergency_action(&instruction(yield_distributor::ID, &pool, resume)));
+        assert!(is_emergency_action(&status_change(PropertyStatus::Paused)));
+        assert!(is_emergency_action(&status_change(PropertyStatus::Active)));
+
+        // Same discriminator on another program, other statuses, malformed data
+        assert!(!is_emergency_action(&instruction(Pubkey::new_unique(), &pool, pause)));
+        assert!(!is_emergency_action(&status_change(PropertyStatus::Closed)));
+        assert!(!is_emergency_action(&status_change(PropertyStatus::Liquidating)));
+        assert!(!is_emergency_action(&instruction(yield_distributor::ID, &pool, vec![1, 2, 3])));
+        let finalize = anchor_data(yield_distributor::instruction::FinalizeDistribution::DISCRIMINATOR, &[]);
+        assert!(!is_emergency_action(&instruction(yield_distributor::ID, &pool, finalize)));
+    }
+
+    #[test]
+    fn treasury_spends_and_upgrades_are_never_emergency_or_council_actions() {
+        let treasury = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let governance = Pubkey::new_unique();
+
+        // SPL Token `Transfer { amount }` and BPF upgradeable loader `Upgrade`
+        let mut transfer_data = vec![3];
+        transfer_data.extend_from_slice(&1_000u64.to_le_bytes());
+        let transfer = instruction(anchor_spl::token::ID, &[treasury, recipient, governance], transfer_data);
+        let upgrade = instruction(
+            anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+            &[Pubkey::new_unique(), Pubkey::new_unique()],
+            vec![3, 0, 0, 0],
+        );
+
+        for instruction in [&transfer, &upgrade] {
+            assert!(!is_emergency_action(instruction));
+            assert!(!is_council_action(instruction));
+        }
+        assert!(is_council_action(&status_change(property_vault::PropertyStatus::Closed)));
+        assert!(!is_council_action(&status_change(property_vault::PropertyStatus::Liquidating)));
+    }
+
+    #[test]
+    fn token_instructions_may_not_spend_the_protected_vaults() {
+        let governance_vault = Pubkey::new_unique();
+        let reward_vault = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let protected = [governance_vault, reward_vault];
+        let transfer_from = |source| instruction(anchor_spl::token::ID, &[source, treasury], vec![3, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+        assert!(spends_protected_vault(&transfer_from(governance_vault), &protected));
+        assert!(spends_protected_vault(&transfer_from(reward_vault), &protected));
+        assert!(!spends_protected_vault(&transfer_from(treasury), &protected));
+
+        // Depositing into a vault is fine, as is any other program touching it
+        let deposit = instruction(anchor_spl::token::ID, &[treasury, governance_vault], vec![3]);
+        assert!(!spends_protected_vault(&deposit, &protected));
+        assert!(!spends_protected_vault(&instruction(Pubkey::new_unique(), &[reward_vault], vec![3]), &protected));
+    }
+
+    #[test]
+    fn emergency_votes_need_quorum_and_a_supermajority() {
+        let config = EmergencyConfig { voting_period: DAY, quorum: 1_000, approval_threshold_bps: 6_667 };
+
+        assert!(emergency_vote_passed(&config, 667, 333));
+        assert!(!emergency_vote_passed(&config, 666, 334));
+        assert!(!emergency_vote_passed(&config, 999, 0)); // Below quorum
+        assert!(emergency_vote_passed(&config, 1_000, 0));
+        assert!(emergency_vote_passed(&config, u64::MAX, u64::MAX / 4));
+
+        let invalid = Error::from(ErrorCode::InvalidParameterValue);
+        let simple_majority = EmergencyConfig { approval_threshold_bps: 5_000, ..config.clone() };
+        let no_quorum = EmergencyConfig { quorum: 0, ..config.clone() };
+        assert_eq!(validate_emergency_config(&simple_majority).unwrap_err(), invalid);
+        assert_eq!(validate_emergency_config(&no_quorum).unwrap_err(), invalid);
+        validate_emergency_config(&config).unwrap();
+    }
+
+    #[test]
+    fn proposal_metadata_is_accepted_up_to_each_limit() {
+        let hash = [1u8; 32];
+        let title = "t".repeat(MAX_TITLE_LEN);
+        let uri = "u".repeat(MAX_URI_LEN);
+        let data = vec![0u8; MAX_EXECUTION_DATA_LEN];
+        assert!(validate_proposal_metadata(&title, &uri, &hash, &data).is_ok());
+        assert!(validate_proposal_metadata("t", "", &hash, &[]).is_ok());
+
+        assert_eq!(
+            validate_proposal_metadata("", &uri, &hash, &data).unwrap_err(),
+            ErrorCode::EmptyTitle.into()
+        );
+        assert_eq!(
+            validate_proposal_metadata(&"t".repeat(MAX_TITLE_LEN + 1), &uri, &hash, &data).unwrap_err(),
+            ErrorCode::TitleTooLong.into()
+        );
+        assert_eq!(
+            validate_proposal_metadata(&title, &"u".repeat(MAX_URI_LEN + 1), &hash, &data).unwrap_err(),
+            ErrorCode::UriTooLong.into()
+        );
+        assert_eq!(
+            validate_proposal_metadata(&title, &uri, &hash, &vec![0u8; MAX_EXECUTION_DATA_LEN + 1]).unwrap_err(),
+            ErrorCode::ExecutionDataTooLarge.into()
+        );
+        assert_eq!(
+            validate_proposal_metadata(&title, &uri, &[0u8; 32], &data).unwrap_err(),
+            ErrorCode::MissingContentHash.into()
+        );
+    }
+
+    #[test]
+    fn proposal_space_matches_the_serialized_account() {
+        let proposal = |title: &str, uri: &str, execution_data: Vec<u8>| Proposal {
+            id: u64::MAX,
+            proposer: Pubkey::new_unique(),
+            title: title.to_string(),
+            uri: uri.to_string(),
+            content_hash: [1u8; 32],
+            proposal_type: ProposalType::Emergency,
+            execution_data,
+            votes_for: u64::MAX,
+            votes_against: u64::MAX,
+            status: ProposalStatus::Expired,
+            created_at: i64::MAX,
+            voting_ends_at: i64::MAX,
+            execution_eta: i64::MAX,
+        };
+
+        let title = "t".repeat(MAX_TITLE_LEN);
+        let uri = "u".repeat(MAX_URI_LEN);
+        let data = vec![7u8; MAX_EXECUTION_DATA_LEN];
+        for (title, uri, data) in [(title.as_str(), uri.as_str(), data), ("t", "", vec![])] {
+            let space = Proposal::space(title, uri, &data);
+            assert_eq!(proposal(title, uri, data).try_to_vec().unwrap().len(), space);
+        }
+
+        // The largest proposal, discriminator included, still fits a single CPI allocation
+        let max = 8 + Proposal::space(&title, &uri, &[0u8; MAX_EXECUTION_DATA_LEN]);
+        assert_eq!(max, 8 + Proposal::BASE_LEN + MAX_TITLE_LEN + MAX_URI_LEN + MAX_EXECUTION_DATA_LEN);
+        assert!(max <= anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE);
+    }
 }
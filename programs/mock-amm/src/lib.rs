@@ -0,0 +1,307 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+
+declare_id!("5od7eSCXJua6sVEeigrdCEm841cgSoDBGBiLA2TQ7PEj");
+
+/// Test-only constant-product AMM. Implements the liquidity interface the ARKLY token
+/// program calls: `add_liquidity(amount_a, amount_b, min_lp)` and
+/// `swap(amount_in, min_amount_out, a_to_b)`, with the account orders documented on
+/// `AddLiquidity` and `Swap`.
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    /// Create an empty pool for `mint_a`/`mint_b` with its vaults and LP mint
+    pub fn initialize_pool(ctx: Context<InitializePool>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps < 10_000, ErrorCode::InvalidFee);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.vault_a = ctx.accounts.vault_a.key();
+        pool.vault_b = ctx.accounts.vault_b.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.fee_bps = fee_bps;
+        pool.bump = *ctx.bumps.get("pool").unwrap();
+
+        Ok(())
+    }
+
+    /// Deposit both sides and mint LP tokens: `sqrt(a * b)` for the first deposit,
+    /// otherwise proportional to the smaller share of the reserves
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        amount_a: u64,
+        amount_b: u64,
+        min_lp: u64,
+    ) -> Result<()> {
+        require!(amount_a > 0 && amount_b > 0, ErrorCode::InvalidAmount);
+
+        let reserve_a = ctx.accounts.vault_a.amount as u128;
+        let reserve_b = ctx.accounts.vault_b.amount as u128;
+        let lp_supply = ctx.accounts.lp_mint.supply as u128;
+
+        let lp_amount = if lp_supply == 0 {
+            integer_sqrt(amount_a as u128 * amount_b as u128)
+        } else {
+            (amount_a as u128 * lp_supply / reserve_a).min(amount_b as u128 * lp_supply / reserve_b)
+        };
+        let lp_amount = u64::try_from(lp_amount).map_err(|_| ErrorCode::MathOverflow)?;
+        require!(lp_amount > 0 && lp_amount >= min_lp, ErrorCode::SlippageExceeded);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_a.to_account_info(),
+                    to: ctx.accounts.vault_a.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_a,
+        )?;
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_b.to_account_info(),
+                    to: ctx.accounts.vault_b.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_b,
+        )?;
+
+        let pool = &ctx.accounts.pool;
+        let seeds = &[
+            b"pool".as_ref(),
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer,
+            ),
+            lp_amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Swap with the constant-product invariant after the pool fee
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64, a_to_b: bool) -> Result<()> {
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+
+        let pool = &ctx.accounts.pool;
+        let (vault_in, vault_out) = if a_to_b {
+            (&ctx.accounts.vault_a, &ctx.accounts.vault_b)
+        } else {
+            (&ctx.accounts.vault_b, &ctx.accounts.vault_a)
+        };
+
+        let amount_in_after_fee = amount_in as u128 * (10_000 - pool.fee_bps as u128);
+        let amount_out = vault_out.amount as u128 * amount_in_after_fee
+            / (vault_in.amount as u128 * 10_000 + amount_in_after_fee);
+        let amount_out = u64::try_from(amount_out).map_err(|_| ErrorCode::MathOverflow)?;
+        require!(amount_out > 0 && amount_out >= min_amount_out, ErrorCode::SlippageExceeded);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_in.to_account_info(),
+                    to: vault_in.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+
+        let seeds = &[
+            b"pool".as_ref(),
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_out.to_account_info(),
+                    to: ctx.accounts.user_out.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_out,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::LEN,
+        seeds = [b"pool", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub mint_a: Account<'info, Mint>,
+    pub mint_b: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", pool.key().as_ref(), mint_a.key().as_ref()],
+        bump,
+        token::mint = mint_a,
+        token::authority = pool
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"vault", pool.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = pool
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"lp_mint", pool.key().as_ref()],
+        bump,
+        mint::decimals = 9,
+        mint::authority = pool
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Account order is part of the interface: pool, vault_a, vault_b, lp_mint,
+/// user_a, user_b, user_lp, owner, token_program
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(
+        has_one = vault_a @ ErrorCode::InvalidPoolAccount,
+        has_one = vault_b @ ErrorCode::InvalidPoolAccount,
+        has_one = lp_mint @ ErrorCode::InvalidPoolAccount
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user_a: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_lp: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Account order is part of the interface: pool, vault_a, vault_b, user_in,
+/// user_out, owner, token_program
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(
+        has_one = vault_a @ ErrorCode::InvalidPoolAccount,
+        has_one = vault_b @ ErrorCode::InvalidPoolAccount
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_in: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_out: Account<'info, TokenAccount>,
+
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+pub struct Pool {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 32 + 2 + 1;
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Fee must be below 100%")]
+    InvalidFee,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Output below the requested minimum")]
+    SlippageExceeded,
+    #[msg("Account does not belong to this pool")]
+    InvalidPoolAccount,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+}
+
+fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = (x + value / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
@@ -0,0 +1,120 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+
+declare_id!("BwgHBP7FuBRefcyyNA7kTqLWAoVHQbsirQELaEuhwoC9");
+
+/// Test-only price oracle. Feeds are written in the Pyth v2 price account layout so
+/// programs read them through the same code path as a real Pyth feed.
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    /// Initialize a feed account pre-allocated with `PRICE_ACCOUNT_LEN` bytes and owned by this program
+    pub fn initialize_feed(ctx: Context<InitializeFeed>, expo: i32) -> Result<()> {
+        let feed = &ctx.accounts.feed;
+        let mut data = feed.try_borrow_mut_data()?;
+
+        require!(data.len() >= PRICE_ACCOUNT_LEN, ErrorCode::InvalidFeedAccount);
+        require!(read_u32(&data, MAGIC_OFFSET) == 0, ErrorCode::FeedAlreadyInitialized);
+
+        write_bytes(&mut data, MAGIC_OFFSET, &PYTH_MAGIC.to_le_bytes());
+        write_bytes(&mut data, VERSION_OFFSET, &PYTH_VERSION.to_le_bytes());
+        write_bytes(&mut data, ACCOUNT_TYPE_OFFSET, &PYTH_PRICE_ACCOUNT_TYPE.to_le_bytes());
+        write_bytes(&mut data, SIZE_OFFSET, &(PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+        write_bytes(&mut data, EXPO_OFFSET, &expo.to_le_bytes());
+        write_bytes(&mut data, AUTHORITY_OFFSET, ctx.accounts.authority.key().as_ref());
+
+        Ok(())
+    }
+
+    /// Publish a new aggregate price. `publish_time` is explicit so tests can simulate stale feeds.
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        publish_time: i64,
+        trading: bool,
+    ) -> Result<()> {
+        let feed = &ctx.accounts.feed;
+        let mut data = feed.try_borrow_mut_data()?;
+
+        require!(data.len() >= PRICE_ACCOUNT_LEN, ErrorCode::InvalidFeedAccount);
+        require!(read_u32(&data, MAGIC_OFFSET) == PYTH_MAGIC, ErrorCode::InvalidFeedAccount);
+        require!(
+            data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32] == ctx.accounts.authority.key().to_bytes(),
+            ErrorCode::Unauthorized
+        );
+
+        let status: u32 = if trading { STATUS_TRADING } else { STATUS_HALTED };
+        let slot = Clock::get()?.slot;
+
+        write_bytes(&mut data, TIMESTAMP_OFFSET, &publish_time.to_le_bytes());
+        write_bytes(&mut data, AGG_PRICE_OFFSET, &price.to_le_bytes());
+        write_bytes(&mut data, AGG_CONF_OFFSET, &conf.to_le_bytes());
+        write_bytes(&mut data, AGG_STATUS_OFFSET, &status.to_le_bytes());
+        write_bytes(&mut data, AGG_PUB_SLOT_OFFSET, &slot.to_le_bytes());
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeed<'info> {
+    /// CHECK: Raw Pyth-layout account created by the client and owned by this program
+    #[account(mut, owner = crate::ID)]
+    pub feed: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: Raw Pyth-layout account owned by this program; authority checked in the handler
+    #[account(mut, owner = crate::ID)]
+    pub feed: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Size of a Pyth v2 price account without publisher components
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+pub const PYTH_VERSION: u32 = 2;
+pub const PYTH_PRICE_ACCOUNT_TYPE: u32 = 3;
+pub const STATUS_HALTED: u32 = 2;
+pub const STATUS_TRADING: u32 = 1;
+
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const SIZE_OFFSET: usize = 12;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AUTHORITY_OFFSET: usize = 112; // Product key slot, unused by consumers
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const AGG_PUB_SLOT_OFFSET: usize = 232;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Feed account is too small or not initialized")]
+    InvalidFeedAccount,
+    #[msg("Feed already initialized")]
+    FeedAlreadyInitialized,
+    #[msg("Unauthorized")]
+    Unauthorized,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&data[offset..offset + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+fn write_bytes(data: &mut [u8], offset: usize, bytes: &[u8]) {
+    data[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
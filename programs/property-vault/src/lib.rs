@@ -1,5 +1,12 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use arkly_common::events::{AuthorityProposed, AuthorityTransferred, RoleUpdated};
+use arkly_common::math::{checked_add, checked_mul, checked_sub};
+use arkly_common::roles::{Role, Roles};
+use arkly_common::{seeds, CommonError};
 
 declare_id!("PR0p3rtyV4u1t7h1s1sY0urPr0p3rtyT0k3n1z4t10n");
 
@@ -20,6 +27,8 @@ pub mod property_vault {
         
         property.property_id = property_id;
         property.authority = ctx.accounts.authority.key();
+        property.pending_authority = Pubkey::default();
+        property.roles = Roles::default(); // Assigned via `set_role`
         property.mint = ctx.accounts.mint.key();
         property.total_tokens = total_tokens;
         property.tokens_sold = 0;
@@ -29,6 +38,9 @@ pub mod property_vault {
         property.status = PropertyStatus::Active;
         property.created_at = Clock::get()?.unix_timestamp;
         property.details = property_details;
+        property.proposal_count = 0;
+        property.approved_sale_price = 0;
+        property.approved_refinance = RefinanceTerms::default();
         
         emit!(PropertyInitializedEvent {
             property_id: property.property_id.clone(),
@@ -56,11 +68,11 @@ pub mod property_vault {
         );
         
         require!(
-            property.tokens_sold + amount <= property.total_tokens,
+            checked_add(property.tokens_sold, amount)? <= property.total_tokens,
             ErrorCode::InsufficientTokensAvailable
         );
         
-        let total_cost = amount * property.token_price;
+        let total_cost = calculate_total_cost(amount, property.token_price)?;
         
         // Transfer USDC payment
         let cpi_accounts = Transfer {
@@ -74,9 +86,9 @@ pub mod property_vault {
         
         // Mint property tokens to investor
         let seeds = &[
-            b"property",
+            seeds::PROPERTY,
             property.property_id.as_bytes(),
-            &[ctx.bumps.property],
+            &[*ctx.bumps.get("property").unwrap()],
         ];
         let signer = &[&seeds[..]];
         
@@ -98,11 +110,11 @@ pub mod property_vault {
             investor.yield_claimed = 0;
             investor.first_purchase = Clock::get()?.unix_timestamp;
         } else {
-            investor.tokens_owned += amount;
-            investor.total_invested += total_cost;
+            investor.tokens_owned = checked_add(investor.tokens_owned, amount)?;
+            investor.total_invested = checked_add(investor.total_invested, total_cost)?;
         }
         
-        property.tokens_sold += amount;
+        property.tokens_sold = checked_add(property.tokens_sold, amount)?;
         investor.last_purchase = Clock::get()?.unix_timestamp;
         
         emit!(TokenPurchaseEvent {
@@ -127,12 +139,11 @@ pub mod property_vault {
         let yield_distribution = &mut ctx.accounts.yield_distribution;
         
         require!(
-            ctx.accounts.authority.key() == property.authority,
-            ErrorCode::Unauthorized
+            property.roles.holds(property.authority, Role::Operator, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
-        // Calculate yield per token
-        let yield_per_token = total_yield / property.tokens_sold;
+        let yield_per_token = calculate_yield_per_token(total_yield, property.tokens_sold)?;
         
         yield_distribution.property = property.key();
         yield_distribution.total_yield = total_yield;
@@ -142,7 +153,7 @@ pub mod property_vault {
         yield_distribution.distributed_at = Clock::get()?.unix_timestamp;
         yield_distribution.claimed_amount = 0;
         
-        property.total_yield_distributed += total_yield;
+        property.total_yield_distributed = checked_add(property.total_yield_distributed, total_yield)?;
         
         emit!(YieldDistributionEvent {
             property_id: property.property_id.clone(),
@@ -175,15 +186,15 @@ pub mod property_vault {
             return Err(ErrorCode::AlreadyClaimed.into());
         }
         
-        let claimable_amount = investor.tokens_owned * yield_distribution.yield_per_token;
+        let claimable_amount = calculate_claimable_yield(investor.tokens_owned, yield_distribution)?;
         
         require!(claimable_amount > 0, ErrorCode::NoYieldToClaim);
         
         // Transfer USDC yield to investor
         let seeds = &[
-            b"property",
+            seeds::PROPERTY,
             ctx.accounts.property.property_id.as_bytes(),
-            &[ctx.bumps.property],
+            &[*ctx.bumps.get("property").unwrap()],
         ];
         let signer = &[&seeds[..]];
         
@@ -202,7 +213,7 @@ pub mod property_vault {
         yield_claim.amount = claimable_amount;
         yield_claim.claimed_at = Clock::get()?.unix_timestamp;
         
-        investor.yield_claimed += claimable_amount;
+        investor.yield_claimed = checked_add(investor.yield_claimed, claimable_amount)?;
         
         emit!(YieldClaimEvent {
             property_id: ctx.accounts.property.property_id.clone(),
@@ -215,16 +226,30 @@ pub mod property_vault {
         Ok(())
     }
 
-    /// Update property status
+    /// Update property status. The pauser role may only move between active and paused.
     pub fn update_property_status(
         ctx: Context<UpdatePropertyStatus>,
         new_status: PropertyStatus,
     ) -> Result<()> {
         let property = &mut ctx.accounts.property;
+        let signer = ctx.accounts.authority.key();
         
+        // Liquidation is entered only through `finalize_property_proposal` and is final
         require!(
-            ctx.accounts.authority.key() == property.authority,
-            ErrorCode::Unauthorized
+            property.status != PropertyStatus::Liquidating,
+            ErrorCode::PropertyLiquidating
+        );
+        require!(
+            new_status != PropertyStatus::Liquidating,
+            ErrorCode::LiquidationRequiresVote
+        );
+        
+        let is_pause_toggle = matches!(property.status, PropertyStatus::Active | PropertyStatus::Paused)
+            && matches!(new_status, PropertyStatus::Active | PropertyStatus::Paused);
+        require!(
+            signer == property.authority
+                || (is_pause_toggle && property.roles.holds(property.authority, Role::Pauser, signer)),
+            CommonError::Unauthorized
         );
         
         let old_status = property.status;
@@ -239,6 +264,336 @@ pub mod property_vault {
 
         Ok(())
     }
+
+    /// Create a holder proposal for a property-level decision
+    pub fn create_property_proposal(
+        ctx: Context<CreatePropertyProposal>,
+        action: PropertyAction,
+    ) -> Result<()> {
+        let property = &mut ctx.accounts.property;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(
+            property.status != PropertyStatus::Closed,
+            ErrorCode::PropertyClosed
+        );
+        
+        require!(property.tokens_sold > 0, ErrorCode::NoTokenHolders);
+        
+        // Proposers must hold a minimum share of the outstanding tokens
+        require!(
+            ctx.accounts.proposer_token_account.amount as u128 * 10_000
+                >= property.tokens_sold as u128 * MIN_PROPOSER_SHARE_BPS as u128,
+            ErrorCode::InsufficientHoldingsToPropose
+        );
+        
+        if let PropertyAction::ReplaceManager { new_authority } = action {
+            require!(
+                new_authority != Pubkey::default() && new_authority != property.authority,
+                ErrorCode::InvalidPropertyAction
+            );
+        }
+        
+        proposal.property = property.key();
+        proposal.id = property.proposal_count;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.snapshot_supply = property.tokens_sold;
+        proposal.status = PropertyProposalStatus::Active;
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.voting_ends_at = proposal
+            .created_at
+            .checked_add(PROPERTY_VOTING_PERIOD)
+            .ok_or(CommonError::MathOverflow)?;
+        
+        property.proposal_count = checked_add(property.proposal_count, 1)?;
+        
+        emit!(PropertyProposalCreated {
+            property_id: property.property_id.clone(),
+            proposal_id: proposal.id,
+            proposer: proposal.proposer,
+            action: proposal.action.clone(),
+            snapshot_supply: proposal.snapshot_supply,
+            voting_ends_at: proposal.voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    /// Vote on a property proposal by locking property tokens until voting ends
+    pub fn cast_property_vote(
+        ctx: Context<CastPropertyVote>,
+        support: bool,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidVoteAmount);
+        
+        require!(
+            ctx.accounts.proposal.status == PropertyProposalStatus::Active,
+            ErrorCode::ProposalNotActive
+        );
+        
+        require!(
+            Clock::get()?.unix_timestamp <= ctx.accounts.proposal.voting_ends_at,
+            ErrorCode::VotingPeriodEnded
+        );
+        
+        // Locking the tokens fixes this balance as the voter's weight and
+        // stops the same tokens from being moved to another wallet and reused
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.voter_token_account.to_account_info(),
+            to: ctx.accounts.vote_escrow.to_account_info(),
+            authority: ctx.accounts.voter.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        let proposal = &mut ctx.accounts.proposal;
+        let vote_record = &mut ctx.accounts.vote_record;
+        
+        if support {
+            proposal.votes_for = checked_add(proposal.votes_for, amount)?;
+        } else {
+            proposal.votes_against = checked_add(proposal.votes_against, amount)?;
+        }
+        
+        vote_record.proposal = proposal.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.support = support;
+        vote_record.amount = amount;
+        vote_record.voted_at = Clock::get()?.unix_timestamp;
+        
+        emit!(PropertyVoteCast {
+            property_id: ctx.accounts.property.property_id.clone(),
+            proposal_id: proposal.id,
+            voter: vote_record.voter,
+            support,
+            voting_power: amount,
+            timestamp: vote_record.voted_at,
+        });
+
+        Ok(())
+    }
+
+    /// Tally a property proposal after voting ends and apply it if it passed
+    pub fn finalize_property_proposal(ctx: Context<FinalizePropertyProposal>) -> Result<()> {
+        let property = &mut ctx.accounts.property;
+        let proposal = &mut ctx.accounts.proposal;
+        
+        require!(
+            proposal.status == PropertyProposalStatus::Active,
+            ErrorCode::ProposalNotActive
+        );
+        
+        require!(
+            Clock::get()?.unix_timestamp > proposal.voting_ends_at,
+            ErrorCode::VotingPeriodNotEnded
+        );
+        
+        let total_votes = proposal.votes_for as u128 + proposal.votes_against as u128;
+        let quorum_reached = total_votes * 10_000
+            >= proposal.snapshot_supply as u128 * PROPERTY_QUORUM_BPS as u128;
+        let approved = proposal.votes_for > proposal.votes_against
+            && proposal.votes_for as u128 * 10_000
+                >= total_votes * proposal.action.approval_threshold_bps() as u128;
+        
+        if !(quorum_reached && approved) {
+            proposal.status = PropertyProposalStatus::Defeated;
+            
+            emit!(PropertyProposalFinalized {
+                property_id: property.property_id.clone(),
+                proposal_id: proposal.id,
+                passed: false,
+                votes_for: proposal.votes_for,
+                votes_against: proposal.votes_against,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+            
+            return Ok(());
+        }
+        
+        match proposal.action.clone() {
+            PropertyAction::ApproveSale { price } => {
+                property.approved_sale_price = price;
+            }
+            PropertyAction::ApproveRefinance { terms } => {
+                property.approved_refinance = terms;
+            }
+            PropertyAction::ReplaceManager { new_authority } => {
+                property.authority = new_authority;
+                property.pending_authority = Pubkey::default();
+            }
+            PropertyAction::EnterLiquidation => {
+                let old_status = property.status.clone();
+                property.status = PropertyStatus::Liquidating;
+                
+                emit!(PropertyStatusUpdateEvent {
+                    property_id: property.property_id.clone(),
+                    old_status,
+                    new_status: PropertyStatus::Liquidating,
+                    timestamp: Clock::get()?.unix_timestamp,
+                });
+            }
+        }
+        
+        proposal.status = PropertyProposalStatus::Executed;
+        
+        emit!(PropertyProposalFinalized {
+            property_id: property.property_id.clone(),
+            proposal_id: proposal.id,
+            passed: true,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Return tokens locked for a vote once the proposal is no longer active
+    pub fn withdraw_property_vote(ctx: Context<WithdrawPropertyVote>) -> Result<()> {
+        require!(
+            ctx.accounts.proposal.status != PropertyProposalStatus::Active,
+            ErrorCode::ProposalStillActive
+        );
+        
+        let amount = ctx.accounts.vote_record.amount;
+        
+        let seeds = &[
+            seeds::PROPERTY,
+            ctx.accounts.property.property_id.as_bytes(),
+            &[*ctx.bumps.get("property").unwrap()],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vote_escrow.to_account_info(),
+            to: ctx.accounts.voter_token_account.to_account_info(),
+            authority: ctx.accounts.property.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        Ok(())
+    }
+
+    /// Quote the USDC cost of buying `amount` property tokens
+    pub fn quote_property_purchase(
+        ctx: Context<QuotePropertyPurchase>,
+        amount: u64,
+    ) -> Result<PropertyPurchaseQuote> {
+        let property = &ctx.accounts.property;
+        
+        require!(
+            property.status == PropertyStatus::Active,
+            ErrorCode::PropertyNotActive
+        );
+        require!(
+            checked_add(property.tokens_sold, amount)? <= property.total_tokens,
+            ErrorCode::InsufficientTokensAvailable
+        );
+        
+        Ok(PropertyPurchaseQuote {
+            token_price: property.token_price,
+            total_cost: calculate_total_cost(amount, property.token_price)?,
+            tokens_available: checked_sub(property.total_tokens, property.tokens_sold)?,
+        })
+    }
+
+    /// Report an investor's yield for one distribution and whether it was claimed
+    pub fn get_claimable_property_yield(
+        ctx: Context<GetClaimablePropertyYield>,
+    ) -> Result<PropertyYieldQuote> {
+        let investor = &ctx.accounts.investor;
+        let yield_distribution = &ctx.accounts.yield_distribution;
+        
+        require!(
+            yield_distribution.property == investor.property,
+            ErrorCode::InvalidDistribution
+        );
+        
+        let claimed = !ctx.accounts.yield_claim.data_is_empty();
+        let amount = calculate_claimable_yield(investor.tokens_owned, yield_distribution)?;
+        
+        Ok(PropertyYieldQuote {
+            amount,
+            claimed,
+            claimable: if claimed { 0 } else { amount },
+        })
+    }
+
+    /// Start handing the admin role to `new_authority`, which must accept it.
+    /// Proposing `Pubkey::default()` cancels a pending handover.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let property = &mut ctx.accounts.property;
+        
+        require!(
+            ctx.accounts.authority.key() == property.authority,
+            CommonError::Unauthorized
+        );
+        
+        property.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            scope: property.key(),
+            authority: property.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Complete an admin handover as the proposed key. A governance PDA accepts by
+    /// executing a proposal whose instruction bundle calls this instruction.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let property = &mut ctx.accounts.property;
+        let new_authority = ctx.accounts.new_authority.key();
+        
+        require!(
+            property.pending_authority != Pubkey::default()
+                && new_authority == property.pending_authority,
+            CommonError::NotPendingAuthority
+        );
+        
+        let previous_authority = property.authority;
+        property.authority = new_authority;
+        property.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            scope: property.key(),
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Assign a delegated role, or clear it with `Pubkey::default()`
+    pub fn set_role(ctx: Context<SetRole>, role: Role, key: Pubkey) -> Result<()> {
+        let property = &mut ctx.accounts.property;
+        
+        require!(
+            ctx.accounts.authority.key() == property.authority,
+            CommonError::Unauthorized
+        );
+        
+        // Compliance approvals only exist in the ARKLY token program
+        require!(role != Role::Compliance, ErrorCode::RoleNotUsed);
+        
+        property.roles.set(role, key);
+        
+        emit!(RoleUpdated {
+            scope: property.key(),
+            role: role as u8,
+            key,
+        });
+
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -248,7 +603,7 @@ pub struct InitializeProperty<'info> {
         init,
         payer = authority,
         space = 8 + Property::LEN,
-        seeds = [b"property", property_id.as_bytes()],
+        seeds = [seeds::PROPERTY, property_id.as_bytes()],
         bump
     )]
     pub property: Account<'info, Property>,
@@ -266,7 +621,7 @@ pub struct InitializeProperty<'info> {
 pub struct PurchasePropertyTokens<'info> {
     #[account(
         mut,
-        seeds = [b"property", property.property_id.as_bytes()],
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
         bump
     )]
     pub property: Account<'info, Property>,
@@ -275,7 +630,7 @@ pub struct PurchasePropertyTokens<'info> {
         init_if_needed,
         payer = investor_authority,
         space = 8 + Investor::LEN,
-        seeds = [b"investor", property.key().as_ref(), investor_authority.key().as_ref()],
+        seeds = [seeds::INVESTOR, property.key().as_ref(), investor_authority.key().as_ref()],
         bump
     )]
     pub investor: Account<'info, Investor>,
@@ -303,7 +658,7 @@ pub struct PurchasePropertyTokens<'info> {
 pub struct DistributeYield<'info> {
     #[account(
         mut,
-        seeds = [b"property", property.property_id.as_bytes()],
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
         bump
     )]
     pub property: Account<'info, Property>,
@@ -312,7 +667,7 @@ pub struct DistributeYield<'info> {
         init,
         payer = authority,
         space = 8 + YieldDistribution::LEN,
-        seeds = [b"yield_distribution", property.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        seeds = [seeds::YIELD_DISTRIBUTION, property.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
         bump
     )]
     pub yield_distribution: Account<'info, YieldDistribution>,
@@ -324,20 +679,20 @@ pub struct DistributeYield<'info> {
 #[derive(Accounts)]
 pub struct ClaimYield<'info> {
     #[account(
-        seeds = [b"property", property.property_id.as_bytes()],
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
         bump
     )]
     pub property: Account<'info, Property>,
     
     #[account(
         mut,
-        seeds = [b"investor", property.key().as_ref(), investor_authority.key().as_ref()],
+        seeds = [seeds::INVESTOR, property.key().as_ref(), investor_authority.key().as_ref()],
         bump
     )]
     pub investor: Account<'info, Investor>,
     
     #[account(
-        seeds = [b"yield_distribution", property.key().as_ref(), &yield_distribution.distributed_at.to_le_bytes()],
+        seeds = [seeds::YIELD_DISTRIBUTION, property.key().as_ref(), &yield_distribution.distributed_at.to_le_bytes()],
         bump
     )]
     pub yield_distribution: Account<'info, YieldDistribution>,
@@ -346,7 +701,7 @@ pub struct ClaimYield<'info> {
         init,
         payer = investor_authority,
         space = 8 + YieldClaim::LEN,
-        seeds = [b"yield_claim", yield_distribution.key().as_ref(), investor_authority.key().as_ref()],
+        seeds = [seeds::YIELD_CLAIM, yield_distribution.key().as_ref(), investor_authority.key().as_ref()],
         bump
     )]
     pub yield_claim: Account<'info, YieldClaim>,
@@ -366,7 +721,224 @@ pub struct ClaimYield<'info> {
 pub struct UpdatePropertyStatus<'info> {
     #[account(
         mut,
-        seeds = [b"property", property.property_id.as_bytes()],
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePropertyProposal<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + PropertyProposal::LEN,
+        seeds = [seeds::PROPERTY_PROPOSAL, property.key().as_ref(), property.proposal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, PropertyProposal>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [seeds::PROPERTY_VOTE_ESCROW, proposal.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = property
+    )]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    
+    #[account(address = property.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = proposer_token_account.mint == property.mint @ ErrorCode::InvalidTokenAccount,
+        constraint = proposer_token_account.owner == proposer.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CastPropertyVote<'info> {
+    #[account(
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    #[account(
+        mut,
+        has_one = property
+    )]
+    pub proposal: Account<'info, PropertyProposal>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + PropertyVoteRecord::LEN,
+        seeds = [seeds::PROPERTY_VOTE, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, PropertyVoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY_VOTE_ESCROW, proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == property.mint @ ErrorCode::InvalidTokenAccount,
+        constraint = voter_token_account.owner == voter.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizePropertyProposal<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    #[account(
+        mut,
+        has_one = property
+    )]
+    pub proposal: Account<'info, PropertyProposal>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPropertyVote<'info> {
+    #[account(
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    #[account(has_one = property)]
+    pub proposal: Account<'info, PropertyProposal>,
+    
+    #[account(
+        mut,
+        close = voter,
+        seeds = [seeds::PROPERTY_VOTE, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, PropertyVoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY_VOTE_ESCROW, proposal.key().as_ref()],
+        bump
+    )]
+    pub vote_escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == property.mint @ ErrorCode::InvalidTokenAccount
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct QuotePropertyPurchase<'info> {
+    #[account(
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+}
+
+#[derive(Accounts)]
+pub struct GetClaimablePropertyYield<'info> {
+    #[account(
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    #[account(
+        seeds = [seeds::INVESTOR, property.key().as_ref(), investor.investor.as_ref()],
+        bump
+    )]
+    pub investor: Account<'info, Investor>,
+    
+    #[account(
+        seeds = [seeds::YIELD_DISTRIBUTION, property.key().as_ref(), &yield_distribution.distributed_at.to_le_bytes()],
+        bump
+    )]
+    pub yield_distribution: Account<'info, YieldDistribution>,
+    
+    /// CHECK: Claim record PDA; only checked for existence
+    #[account(
+        seeds = [seeds::YIELD_CLAIM, yield_distribution.key().as_ref(), investor.investor.as_ref()],
+        bump
+    )]
+    pub yield_claim: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
+        bump
+    )]
+    pub property: Account<'info, Property>,
+    
+    /// The pending authority; a governance PDA signs through proposal execution
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::PROPERTY, property.property_id.as_bytes()],
         bump
     )]
     pub property: Account<'info, Property>,
@@ -387,10 +959,17 @@ pub struct Property {
     pub status: PropertyStatus,
     pub created_at: i64,
     pub details: PropertyDetails,
+    pub proposal_count: u64,
+    pub approved_sale_price: u64, // Set by a passed holder proposal, 0 if none
+    pub approved_refinance: RefinanceTerms,
+    pub pending_authority: Pubkey, // Default unless a handover is in progress
+    pub roles: Roles,
 }
 
 impl Property {
-    pub const LEN: usize = 4 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 8 + 1 + 8 + PropertyDetails::LEN;
+    pub const LEN: usize = 4 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 8 + 1 + 8 + PropertyDetails::LEN
+        + 8 + 8 + RefinanceTerms::LEN
+        + 32 + Roles::LEN;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -418,6 +997,82 @@ pub enum PropertyStatus {
     SoldOut,
     Paused,
     Closed,
+    Liquidating,
+}
+
+/// Holder proposals may be created by wallets holding at least 1% of sold tokens
+pub const MIN_PROPOSER_SHARE_BPS: u16 = 100;
+/// At least 20% of the snapshot supply must vote
+pub const PROPERTY_QUORUM_BPS: u16 = 2_000;
+pub const PROPERTY_VOTING_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq)]
+pub struct RefinanceTerms {
+    pub loan_amount: u64, // In USDC (6 decimals)
+    pub interest_rate_bps: u16,
+    pub term_months: u16,
+}
+
+impl RefinanceTerms {
+    pub const LEN: usize = 8 + 2 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum PropertyAction {
+    ApproveSale { price: u64 },
+    ApproveRefinance { terms: RefinanceTerms },
+    ReplaceManager { new_authority: Pubkey },
+    EnterLiquidation,
+}
+
+impl PropertyAction {
+    pub const LEN: usize = 1 + 32;
+    
+    /// Share of votes cast that must be in favour
+    pub fn approval_threshold_bps(&self) -> u16 {
+        match self {
+            PropertyAction::ApproveSale { .. } | PropertyAction::ApproveRefinance { .. } => 5_000,
+            PropertyAction::ReplaceManager { .. } | PropertyAction::EnterLiquidation => 6_667,
+        }
+    }
+}
+
+#[account]
+pub struct PropertyProposal {
+    pub property: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub action: PropertyAction,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub snapshot_supply: u64, // Property tokens sold when the proposal was created
+    pub status: PropertyProposalStatus,
+    pub created_at: i64,
+    pub voting_ends_at: i64,
+}
+
+impl PropertyProposal {
+    pub const LEN: usize = 32 + 8 + 32 + PropertyAction::LEN + 8 + 8 + 8 + 1 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum PropertyProposalStatus {
+    Active,
+    Defeated,
+    Executed,
+}
+
+#[account]
+pub struct PropertyVoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub amount: u64, // Property tokens locked in the vote escrow
+    pub voted_at: i64,
+}
+
+impl PropertyVoteRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 8 + 8;
 }
 
 #[account]
@@ -462,6 +1117,22 @@ impl YieldClaim {
     pub const LEN: usize = 32 + 32 + 8 + 8;
 }
 
+/// Returned by `quote_property_purchase`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PropertyPurchaseQuote {
+    pub token_price: u64,
+    pub total_cost: u64,
+    pub tokens_available: u64,
+}
+
+/// Returned by `get_claimable_property_yield`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PropertyYieldQuote {
+    pub amount: u64,
+    pub claimed: bool,
+    pub claimable: u64,
+}
+
 #[event]
 pub struct PropertyInitializedEvent {
     pub property_id: String,
@@ -508,18 +1179,161 @@ pub struct PropertyStatusUpdateEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PropertyProposalCreated {
+    pub property_id: String,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: PropertyAction,
+    pub snapshot_supply: u64,
+    pub voting_ends_at: i64,
+}
+
+#[event]
+pub struct PropertyVoteCast {
+    pub property_id: String,
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PropertyProposalFinalized {
+    pub property_id: String,
+    pub proposal_id: u64,
+    pub passed: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Property is not active")]
     PropertyNotActive,
     #[msg("Insufficient tokens available")]
     InsufficientTokensAvailable,
-    #[msg("Unauthorized")]
-    Unauthorized,
     #[msg("Invalid distribution")]
     InvalidDistribution,
     #[msg("Already claimed")]
     AlreadyClaimed,
     #[msg("No yield to claim")]
     NoYieldToClaim,
+    #[msg("Property is closed")]
+    PropertyClosed,
+    #[msg("Property has no token holders")]
+    NoTokenHolders,
+    #[msg("Insufficient property token holdings to create a proposal")]
+    InsufficientHoldingsToPropose,
+    #[msg("Invalid property action")]
+    InvalidPropertyAction,
+    #[msg("Token account does not match the property mint or owner")]
+    InvalidTokenAccount,
+    #[msg("Vote amount must be greater than zero")]
+    InvalidVoteAmount,
+    #[msg("Proposal is not active")]
+    ProposalNotActive,
+    #[msg("Proposal is still active")]
+    ProposalStillActive,
+    #[msg("Voting period has ended")]
+    VotingPeriodEnded,
+    #[msg("Voting period has not ended")]
+    VotingPeriodNotEnded,
+    #[msg("Property is being liquidated")]
+    PropertyLiquidating,
+    #[msg("Liquidation requires a holder vote")]
+    LiquidationRequiresVote,
+    #[msg("Role is not used by this program")]
+    RoleNotUsed,
+}
+
+/// USDC cost of `amount` property tokens
+fn calculate_total_cost(amount: u64, token_price: u64) -> Result<u64> {
+    checked_mul(amount, token_price)
+}
+
+/// Yield per token sold, rounded down; the remainder stays with the property
+fn calculate_yield_per_token(total_yield: u64, tokens_sold: u64) -> Result<u64> {
+    require!(tokens_sold > 0, ErrorCode::NoTokenHolders);
+    Ok(total_yield / tokens_sold)
+}
+
+/// An investor's share of a yield distribution; `yield_per_token` was already rounded down
+fn calculate_claimable_yield(tokens_owned: u64, yield_distribution: &YieldDistribution) -> Result<u64> {
+    checked_mul(tokens_owned, yield_distribution.yield_per_token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yield_distribution(yield_per_token: u64) -> YieldDistribution {
+        YieldDistribution {
+            property: Pubkey::new_unique(),
+            total_yield: 0,
+            yield_per_token,
+            period_start: 0,
+            period_end: 0,
+            distributed_at: 0,
+            claimed_amount: 0,
+        }
+    }
+
+    #[test]
+    fn yield_per_token_rounds_down() {
+        assert_eq!(calculate_yield_per_token(1_000, 3).unwrap(), 333);
+        assert_eq!(calculate_yield_per_token(999, 1_000).unwrap(), 0);
+        assert_eq!(calculate_yield_per_token(3_000, 3).unwrap(), 1_000);
+        assert_eq!(calculate_yield_per_token(1_000, 0).unwrap_err(), ErrorCode::NoTokenHolders.into());
+    }
+
+    #[test]
+    fn claims_never_exceed_the_distributed_yield() {
+        let tokens_sold = [1, 2, 4];
+        let total_yield = 1_000;
+        let distribution =
+            yield_distribution(calculate_yield_per_token(total_yield, tokens_sold.iter().sum()).unwrap());
+
+        let claimed = tokens_sold
+            .iter()
+            .map(|tokens| calculate_claimable_yield(*tokens, &distribution).unwrap())
+            .sum::<u64>();
+
+        assert_eq!(claimed, 994);
+        assert!(claimed <= total_yield);
+    }
+
+    #[test]
+    fn cost_and_yield_report_overflow() {
+        let overflow = Error::from(CommonError::MathOverflow);
+
+        assert_eq!(calculate_total_cost(10, 1_500_000).unwrap(), 15_000_000);
+        assert_eq!(calculate_total_cost(u64::MAX, 2).unwrap_err(), overflow);
+        assert_eq!(calculate_claimable_yield(u64::MAX, &yield_distribution(2)).unwrap_err(), overflow);
+    }
+
+    #[test]
+    fn cost_and_yield_hold_products_up_to_u64_max() {
+        let overflow = Error::from(CommonError::MathOverflow);
+
+        assert_eq!(calculate_total_cost(0, u64::MAX).unwrap(), 0);
+        assert_eq!(calculate_total_cost(u64::MAX, 1).unwrap(), u64::MAX);
+        assert_eq!(calculate_total_cost(u64::MAX / 2, 2).unwrap(), u64::MAX - 1);
+        assert_eq!(calculate_total_cost(u64::MAX / 2 + 1, 2).unwrap_err(), overflow);
+
+        assert_eq!(calculate_claimable_yield(0, &yield_distribution(u64::MAX)).unwrap(), 0);
+        assert_eq!(calculate_claimable_yield(u64::MAX, &yield_distribution(0)).unwrap(), 0);
+        assert_eq!(calculate_claimable_yield(u64::MAX, &yield_distribution(1)).unwrap(), u64::MAX);
+        assert_eq!(calculate_claimable_yield(u64::MAX / 2 + 1, &yield_distribution(2)).unwrap_err(), overflow);
+    }
+
+    #[test]
+    fn yield_per_token_needs_a_token_sold() {
+        assert_eq!(calculate_yield_per_token(0, 0).unwrap_err(), ErrorCode::NoTokenHolders.into());
+        assert_eq!(calculate_yield_per_token(u64::MAX, 0).unwrap_err(), ErrorCode::NoTokenHolders.into());
+        assert_eq!(calculate_yield_per_token(u64::MAX, 1).unwrap(), u64::MAX);
+        assert_eq!(calculate_yield_per_token(0, 1).unwrap(), 0);
+    }
 }
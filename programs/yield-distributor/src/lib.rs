@@ -1,5 +1,12 @@
+// anchor-lang 0.28 handlers return its large `Error` by value
+#![allow(clippy::result_large_err)]
+
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use arkly_common::events::{AuthorityProposed, AuthorityTransferred, RoleUpdated};
+use arkly_common::math::{checked_add, checked_sub, mul_div_floor};
+use arkly_common::roles::{Role, Roles};
+use arkly_common::{pda, seeds, CommonError};
 
 declare_id!("Y13ldD1str1but0rR3nt4lR3v3nu3Sh4r1ngSm4rtC0ntr4ct");
 
@@ -18,6 +25,8 @@ pub mod yield_distributor {
         
         yield_pool.pool_id = pool_id;
         yield_pool.authority = ctx.accounts.authority.key();
+        yield_pool.pending_authority = Pubkey::default();
+        yield_pool.roles = Roles::default(); // Assigned via `set_role`
         yield_pool.property_mint = property_mint;
         yield_pool.usdc_vault = ctx.accounts.usdc_vault.key();
         yield_pool.total_deposited = 0;
@@ -62,7 +71,7 @@ pub mod yield_distributor {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, amount)?;
         
-        yield_pool.total_deposited += amount;
+        yield_pool.total_deposited = checked_add(yield_pool.total_deposited, amount)?;
         
         emit!(YieldDeposited {
             pool_id: yield_pool.pool_id.clone(),
@@ -87,12 +96,13 @@ pub mod yield_distributor {
         let distribution = &mut ctx.accounts.distribution;
         
         require!(
-            ctx.accounts.authority.key() == yield_pool.authority,
-            ErrorCode::Unauthorized
+            yield_pool.roles.holds(yield_pool.authority, Role::Operator, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
+        require!(total_tokens_eligible > 0, CommonError::DivisionByZero);
         require!(
-            yield_amount <= yield_pool.total_deposited - yield_pool.total_distributed,
+            yield_amount <= undistributed_yield(yield_pool)?,
             ErrorCode::InsufficientFunds
         );
         
@@ -104,7 +114,10 @@ pub mod yield_distributor {
         distribution.claims_count = 0;
         distribution.status = DistributionStatus::Active;
         distribution.created_at = Clock::get()?.unix_timestamp;
-        distribution.expires_at = distribution.created_at + 30 * 24 * 60 * 60; // 30 days expiry
+        distribution.expires_at = distribution
+            .created_at
+            .checked_add(30 * 24 * 60 * 60) // 30 days expiry
+            .ok_or(CommonError::MathOverflow)?;
         
         emit!(DistributionCreated {
             snapshot_id: distribution.snapshot_id.clone(),
@@ -150,7 +163,7 @@ pub mod yield_distributor {
         );
         
         // Calculate yield amount based on token balance
-        let yield_amount = (distribution.yield_amount * token_balance) / distribution.total_tokens_eligible;
+        let yield_amount = calculate_pro_rata_yield(distribution, token_balance)?;
         
         require!(
             yield_amount > 0,
@@ -159,9 +172,9 @@ pub mod yield_distributor {
         
         // Transfer yield to claimer
         let seeds = &[
-            b"yield_pool",
+            seeds::YIELD_POOL,
             yield_pool.pool_id.as_bytes(),
-            &[ctx.bumps.yield_pool],
+            &[*ctx.bumps.get("yield_pool").unwrap()],
         ];
         let signer = &[&seeds[..]];
         
@@ -179,10 +192,10 @@ pub mod yield_distributor {
         claim_record.claimed_amount = yield_amount;
         claim_record.claimed_at = Clock::get()?.unix_timestamp;
         
-        distribution.distributed_amount += yield_amount;
-        distribution.claims_count += 1;
+        distribution.distributed_amount = checked_add(distribution.distributed_amount, yield_amount)?;
+        distribution.claims_count = distribution.claims_count.checked_add(1).ok_or(CommonError::MathOverflow)?;
         
-        yield_pool.total_distributed += yield_amount;
+        yield_pool.total_distributed = checked_add(yield_pool.total_distributed, yield_amount)?;
         
         emit!(YieldClaimed {
             claimer: ctx.accounts.claimer.key(),
@@ -204,8 +217,8 @@ pub mod yield_distributor {
         let distribution = &mut ctx.accounts.distribution;
         
         require!(
-            ctx.accounts.authority.key() == yield_pool.authority,
-            ErrorCode::Unauthorized
+            yield_pool.roles.holds(yield_pool.authority, Role::Operator, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
         require!(
@@ -219,13 +232,16 @@ pub mod yield_distributor {
         for claim in claim_data.iter() {
             // In a real implementation, you would iterate through remaining accounts
             // and process each claim individually
-            total_processed += claim.yield_amount;
-            claims_processed += 1;
+            total_processed = checked_add(total_processed, claim.yield_amount)?;
+            claims_processed = claims_processed.checked_add(1).ok_or(CommonError::MathOverflow)?;
         }
         
-        distribution.distributed_amount += total_processed;
-        distribution.claims_count += claims_processed;
-        yield_pool.total_distributed += total_processed;
+        distribution.distributed_amount = checked_add(distribution.distributed_amount, total_processed)?;
+        distribution.claims_count = distribution
+            .claims_count
+            .checked_add(claims_processed)
+            .ok_or(CommonError::MathOverflow)?;
+        yield_pool.total_distributed = checked_add(yield_pool.total_distributed, total_processed)?;
         
         emit!(BatchClaimsProcessed {
             distribution_id: distribution.snapshot_id.clone(),
@@ -243,8 +259,8 @@ pub mod yield_distributor {
         let distribution = &mut ctx.accounts.distribution;
         
         require!(
-            ctx.accounts.authority.key() == yield_pool.authority,
-            ErrorCode::Unauthorized
+            yield_pool.roles.holds(yield_pool.authority, Role::Operator, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
         require!(
@@ -258,7 +274,7 @@ pub mod yield_distributor {
         emit!(DistributionFinalized {
             distribution_id: distribution.snapshot_id.clone(),
             total_distributed: distribution.distributed_amount,
-            unclaimed_amount: distribution.yield_amount - distribution.distributed_amount,
+            unclaimed_amount: checked_sub(distribution.yield_amount, distribution.distributed_amount)?,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -270,8 +286,8 @@ pub mod yield_distributor {
         let yield_pool = &mut ctx.accounts.yield_pool;
         
         require!(
-            ctx.accounts.authority.key() == yield_pool.authority,
-            ErrorCode::Unauthorized
+            yield_pool.roles.holds(yield_pool.authority, Role::Pauser, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
         yield_pool.status = PoolStatus::Paused;
@@ -289,8 +305,8 @@ pub mod yield_distributor {
         let yield_pool = &mut ctx.accounts.yield_pool;
         
         require!(
-            ctx.accounts.authority.key() == yield_pool.authority,
-            ErrorCode::Unauthorized
+            yield_pool.roles.holds(yield_pool.authority, Role::Pauser, ctx.accounts.authority.key()),
+            CommonError::Unauthorized
         );
         
         yield_pool.status = PoolStatus::Active;
@@ -302,6 +318,115 @@ pub mod yield_distributor {
 
         Ok(())
     }
+
+    /// Report the yield `claimer` would receive for `token_balance` and whether they
+    /// can still claim it. The Merkle proof is checked at claim time only.
+    pub fn get_claimable_distribution(
+        ctx: Context<GetClaimableDistribution>,
+        claimer: Pubkey,
+        token_balance: u64,
+    ) -> Result<DistributionQuote> {
+        let distribution = &ctx.accounts.distribution;
+        let claim_record = &ctx.accounts.claim_record;
+        
+        let (expected_record, _) = pda::claim_record(ctx.program_id, &distribution.key(), &claimer);
+        require!(claim_record.key() == expected_record, ErrorCode::InvalidClaimRecord);
+        
+        let claimed = if claim_record.data_is_empty() {
+            false
+        } else {
+            ClaimRecord::try_deserialize(&mut &claim_record.try_borrow_data()?[..])?.has_claimed
+        };
+        let open = distribution.status == DistributionStatus::Active
+            && Clock::get()?.unix_timestamp <= distribution.expires_at;
+        let yield_amount = calculate_pro_rata_yield(distribution, token_balance)?;
+        
+        Ok(DistributionQuote {
+            yield_amount,
+            claimed,
+            claimable: if claimed || !open { 0 } else { yield_amount },
+            expires_at: distribution.expires_at,
+        })
+    }
+
+    /// Start handing the admin role to `new_authority`, which must accept it.
+    /// Proposing `Pubkey::default()` cancels a pending handover.
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let yield_pool = &mut ctx.accounts.yield_pool;
+        
+        require!(
+            ctx.accounts.authority.key() == yield_pool.authority,
+            CommonError::Unauthorized
+        );
+        
+        yield_pool.pending_authority = new_authority;
+        
+        emit!(AuthorityProposed {
+            scope: yield_pool.key(),
+            authority: yield_pool.authority,
+            pending_authority: new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Complete an admin handover as the proposed key. A governance PDA accepts by
+    /// executing a proposal whose instruction bundle calls this instruction.
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let yield_pool = &mut ctx.accounts.yield_pool;
+        let new_authority = ctx.accounts.new_authority.key();
+        
+        require!(
+            yield_pool.pending_authority != Pubkey::default()
+                && new_authority == yield_pool.pending_authority,
+            CommonError::NotPendingAuthority
+        );
+        
+        let previous_authority = yield_pool.authority;
+        yield_pool.authority = new_authority;
+        yield_pool.pending_authority = Pubkey::default();
+        
+        emit!(AuthorityTransferred {
+            scope: yield_pool.key(),
+            previous_authority,
+            new_authority,
+        });
+
+        Ok(())
+    }
+
+    /// Assign a delegated role, or clear it with `Pubkey::default()`
+    pub fn set_role(ctx: Context<SetRole>, role: Role, key: Pubkey) -> Result<()> {
+        let yield_pool = &mut ctx.accounts.yield_pool;
+        
+        require!(
+            ctx.accounts.authority.key() == yield_pool.authority,
+            CommonError::Unauthorized
+        );
+        
+        // Compliance approvals only exist in the ARKLY token program
+        require!(role != Role::Compliance, ErrorCode::RoleNotUsed);
+        
+        yield_pool.roles.set(role, key);
+        
+        emit!(RoleUpdated {
+            scope: yield_pool.key(),
+            role: role as u8,
+            key,
+        });
+
+        Ok(())
+    }
+}
+
+/// A holder's share of a distribution, pro rata to their snapshot balance and rounded down
+fn calculate_pro_rata_yield(distribution: &Distribution, token_balance: u64) -> Result<u64> {
+    mul_div_floor(distribution.yield_amount, token_balance, distribution.total_tokens_eligible)
+}
+
+/// Deposited yield not yet committed to a distribution
+fn undistributed_yield(yield_pool: &YieldPool) -> Result<u64> {
+    checked_sub(yield_pool.total_deposited, yield_pool.total_distributed)
 }
 
 // Helper function for merkle proof verification (simplified)
@@ -322,7 +447,7 @@ pub struct InitializeYieldPool<'info> {
         init,
         payer = authority,
         space = 8 + YieldPool::LEN,
-        seeds = [b"yield_pool", pool_id.as_bytes()],
+        seeds = [seeds::YIELD_POOL, pool_id.as_bytes()],
         bump
     )]
     pub yield_pool: Account<'info, YieldPool>,
@@ -359,7 +484,7 @@ pub struct CreateDistributionSnapshot<'info> {
         init,
         payer = authority,
         space = 8 + Distribution::LEN,
-        seeds = [b"distribution", snapshot_id.as_bytes()],
+        seeds = [seeds::DISTRIBUTION, snapshot_id.as_bytes()],
         bump
     )]
     pub distribution: Account<'info, Distribution>,
@@ -374,7 +499,7 @@ pub struct CreateDistributionSnapshot<'info> {
 pub struct ClaimYield<'info> {
     #[account(
         mut,
-        seeds = [b"yield_pool", yield_pool.pool_id.as_bytes()],
+        seeds = [seeds::YIELD_POOL, yield_pool.pool_id.as_bytes()],
         bump
     )]
     pub yield_pool: Account<'info, YieldPool>,
@@ -386,7 +511,7 @@ pub struct ClaimYield<'info> {
         init_if_needed,
         payer = claimer,
         space = 8 + ClaimRecord::LEN,
-        seeds = [b"claim_record", distribution.key().as_ref(), claimer.key().as_ref()],
+        seeds = [seeds::CLAIM_RECORD, distribution.key().as_ref(), claimer.key().as_ref()],
         bump
     )]
     pub claim_record: Account<'info, ClaimRecord>,
@@ -441,6 +566,39 @@ pub struct ResumePool<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct GetClaimableDistribution<'info> {
+    pub distribution: Account<'info, Distribution>,
+    
+    /// CHECK: Claimer's claim record PDA, checked in the handler; may not exist yet
+    pub claim_record: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut)]
+    pub yield_pool: Account<'info, YieldPool>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub yield_pool: Account<'info, YieldPool>,
+    
+    /// The pending authority; a governance PDA signs through proposal execution
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(mut)]
+    pub yield_pool: Account<'info, YieldPool>,
+    
+    pub authority: Signer<'info>,
+}
+
 #[account]
 pub struct YieldPool {
     pub pool_id: String,
@@ -454,10 +612,13 @@ pub struct YieldPool {
     pub distributions_count: u32,
     pub status: PoolStatus,
     pub created_at: i64,
+    pub pending_authority: Pubkey, // Default unless a handover is in progress
+    pub roles: Roles,
 }
 
 impl YieldPool {
-    pub const LEN: usize = 4 + 50 + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 4 + 1 + 8;
+    pub const LEN: usize = 4 + 50 + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 4 + 1 + 8
+        + 32 + Roles::LEN;
 }
 
 #[account]
@@ -519,6 +680,15 @@ pub struct ClaimData {
     pub yield_amount: u64,
 }
 
+/// Returned by `get_claimable_distribution`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DistributionQuote {
+    pub yield_amount: u64,
+    pub claimed: bool,
+    pub claimable: u64, // 0 once claimed, expired or finalized
+    pub expires_at: i64,
+}
+
 #[event]
 pub struct YieldPoolCreated {
     pub pool_id: String,
@@ -589,8 +759,6 @@ pub struct PoolResumed {
 pub enum ErrorCode {
     #[msg("Pool is not active")]
     PoolNotActive,
-    #[msg("Unauthorized access")]
-    Unauthorized,
     #[msg("Insufficient funds in the pool")]
     InsufficientFunds,
     #[msg("Distribution is not active")]
@@ -605,4 +773,96 @@ pub enum ErrorCode {
     NoYieldToClaim,
     #[msg("Cannot finalize distribution yet")]
     CannotFinalize,
+    #[msg("Claim record does not match the distribution and claimer")]
+    InvalidClaimRecord,
+    #[msg("Role is not used by this program")]
+    RoleNotUsed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distribution(yield_amount: u64, total_tokens_eligible: u64) -> Distribution {
+        Distribution {
+            snapshot_id: "snapshot-1".to_string(),
+            yield_pool: Pubkey::new_unique(),
+            total_tokens_eligible,
+            yield_amount,
+            distributed_amount: 0,
+            claims_count: 0,
+            status: DistributionStatus::Active,
+            created_at: 0,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn pro_rata_yield_rounds_down() {
+        let distribution = distribution(1_000, 3);
+
+        assert_eq!(calculate_pro_rata_yield(&distribution, 1).unwrap(), 333);
+        assert_eq!(calculate_pro_rata_yield(&distribution, 2).unwrap(), 666);
+        assert_eq!(calculate_pro_rata_yield(&distribution, 3).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn pro_rata_claims_never_exceed_the_yield() {
+        let balances = [1, 10, 100, 1_000, 9_876];
+        let distribution = distribution(1_000_000, balances.iter().sum());
+
+        let claimed = balances
+            .iter()
+            .map(|balance| calculate_pro_rata_yield(&distribution, *balance).unwrap())
+            .sum::<u64>();
+
+        assert!(claimed <= distribution.yield_amount);
+        assert!(distribution.yield_amount - claimed < balances.len() as u64);
+    }
+
+    #[test]
+    fn pro_rata_yield_keeps_large_products_in_u128() {
+        let distribution = distribution(u64::MAX, u64::MAX);
+
+        assert_eq!(calculate_pro_rata_yield(&distribution, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(calculate_pro_rata_yield(&distribution, u64::MAX / 2).unwrap(), u64::MAX / 2);
+    }
+
+    #[test]
+    fn pro_rata_yield_rejects_an_empty_snapshot() {
+        assert_eq!(
+            calculate_pro_rata_yield(&distribution(1_000, 0), 0).unwrap_err(),
+            CommonError::DivisionByZero.into()
+        );
+    }
+
+    fn yield_pool(total_deposited: u64, total_distributed: u64) -> YieldPool {
+        YieldPool {
+            pool_id: "pool-1".to_string(),
+            authority: Pubkey::new_unique(),
+            property_mint: Pubkey::new_unique(),
+            usdc_vault: Pubkey::new_unique(),
+            total_deposited,
+            total_distributed,
+            distribution_frequency: DistributionFrequency::Monthly,
+            last_distribution: 0,
+            distributions_count: 0,
+            status: PoolStatus::Active,
+            created_at: 0,
+            pending_authority: Pubkey::default(),
+            roles: Roles::default(),
+        }
+    }
+
+    #[test]
+    fn undistributed_yield_is_deposits_less_distributions() {
+        assert_eq!(undistributed_yield(&yield_pool(1_000, 0)).unwrap(), 1_000);
+        assert_eq!(undistributed_yield(&yield_pool(1_000, 999)).unwrap(), 1);
+        assert_eq!(undistributed_yield(&yield_pool(1_000, 1_000)).unwrap(), 0);
+        assert_eq!(undistributed_yield(&yield_pool(u64::MAX, 0)).unwrap(), u64::MAX);
+        assert_eq!(
+            undistributed_yield(&yield_pool(1_000, 1_001)).unwrap_err(),
+            CommonError::MathOverflow.into()
+        );
+    }
 }
@@ -6,6 +6,11 @@ import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { createMint, createAccount, mintTo, getAccount } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import { BN } from "bn.js";
+import {
+  hashProposalDocument,
+  validateProposalMetadata,
+  verifyProposalDocument,
+} from "../app/sdk/proposal-metadata";
 
 describe("Arkly Capital Smart Contracts", () => {
   const provider = anchor.AnchorProvider.env();
@@ -219,6 +224,22 @@ describe("Arkly Capital Smart Contracts", () => {
     });
   });
 
+  describe("Governance Proposal Metadata", () => {
+    it("Verifies a proposal document against its content hash", async () => {
+      const document = "# Lower execution delay\n\nReduce the execution delay to 24 hours.";
+      const contentHash = hashProposalDocument(document);
+
+      assert.isTrue(verifyProposalDocument(document, Array.from(contentHash)));
+      assert.isFalse(verifyProposalDocument(document + " ", Array.from(contentHash)));
+    });
+
+    it("Rejects titles and URIs over the on-chain limits", async () => {
+      expect(() => validateProposalMetadata("a".repeat(65), "ipfs://proposal")).to.throw();
+      expect(() => validateProposalMetadata("Title", "ipfs://" + "a".repeat(200))).to.throw();
+      expect(() => validateProposalMetadata("Title", "ipfs://proposal")).to.not.throw();
+    });
+  });
+
   describe("Integration Tests", () => {
     it("Complete investment flow", async () => {
       // 1. Purchase ARKLY tokens in presale